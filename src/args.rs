@@ -1,20 +1,24 @@
+use crate::builder::Alignment;
+#[cfg(feature = "gui")]
+use crate::color::{parse_hex_color, ColorMode, Theme};
+use crate::engine::EngineKind;
 #[cfg(feature = "gui")]
 use crate::gui;
+use crate::patterns::{parse_template, PATTERNS};
+use crate::render::Render;
+use crate::rule::Rule;
 use crate::Board;
-use clap::{Parser, ValueEnum};
+use clap::{builder::PossibleValuesParser, Parser, Subcommand};
+#[cfg(feature = "gui")]
+use std::path::PathBuf;
 use std::str::FromStr;
 
-#[derive(ValueEnum, Copy, Clone, Debug)]
-#[rustfmt::skip]
-pub enum Alignment {
-    TopLeft   , Top   , TopRight   ,
-    Left      , Center, Right      ,
-    BottomLeft, Bottom, BottomRight,
-}
-
 #[derive(Parser, Debug)]
-#[command(version, about)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
 pub(crate) struct Args {
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+
     /// Number of columns of in the board
     #[arg(short, long, default_value_t = 640)]
     pub(crate) cols: usize,
@@ -23,10 +27,18 @@ pub(crate) struct Args {
     #[arg(short, long, default_value_t = 400)]
     pub(crate) rows: usize,
 
-    /// A board template string
-    #[arg(short, long, value_parser = Board::from_str)]
+    /// A board template string, or the name of a built-in pattern (see `patterns list`)
+    #[arg(short, long, value_parser = parse_template)]
     pub(crate) template: Option<Board>,
 
+    /// Rule in B/S notation (e.g. B3/S23) or Larger than Life notation (e.g. R5,C0,M1,S34..58,B34..45,NM)
+    #[arg(short = 'R', long, value_parser = Rule::from_str)]
+    pub(crate) rule: Option<Rule>,
+
+    /// A Golly-style .rule file with a @TABLE (and optional @COLORS) section
+    #[arg(long, value_parser = Rule::load, conflicts_with = "rule")]
+    pub(crate) rule_file: Option<Rule>,
+
     /// Alignment of the template within the world
     #[arg(short, long, value_enum, default_value_t = Alignment::Center)]
     pub(crate) align: Alignment,
@@ -43,6 +55,10 @@ pub(crate) struct Args {
     #[arg(short = 'G', long)]
     pub(crate) generation_limit: Option<usize>,
 
+    /// How generations are computed: each suits different boards, but all give the same results
+    #[arg(long, value_enum, default_value_t = EngineKind::default())]
+    pub(crate) engine: EngineKind,
+
     #[cfg(feature = "gui")]
     /// Scale factor (pixels per cell side)
     #[arg(short, long, default_value_t=2.0, value_parser = gui::valid_scale, conflicts_with = "no_gui")]
@@ -58,16 +74,109 @@ pub(crate) struct Args {
     )]
     pub(crate) exit_on_finish: bool,
 
+    #[cfg(feature = "gui")]
+    /// Number of previous boards kept for undo and stepping backwards in the GUI
+    #[arg(long, default_value_t = 100, conflicts_with = "no_gui")]
+    pub(crate) history: usize,
+
+    #[cfg(any(feature = "gui", feature = "tui"))]
+    /// Number of generations advanced by each press of N in the GUI or terminal UI
+    #[arg(long, default_value_t = 10)]
+    pub(crate) step_size: usize,
+
+    #[cfg(feature = "gui")]
+    /// How to color cells in the GUI and exported images: by state, by age, with trails behind dying cells, or as a heatmap of activity
+    #[arg(long, value_enum, default_value_t = ColorMode::State)]
+    pub(crate) color_mode: ColorMode,
+
+    #[cfg(feature = "gui")]
+    /// Color theme for the GUI and exported images
+    #[arg(long, value_enum, default_value_t = Theme::Dark)]
+    pub(crate) theme: Theme,
+
+    #[cfg(feature = "gui")]
+    /// Color of live cells as hex, e.g. #ffcc00 (overrides the theme)
+    #[arg(long, value_name = "HEX", value_parser = parse_hex_color)]
+    pub(crate) live_color: Option<[u8; 3]>,
+
+    #[cfg(feature = "gui")]
+    /// Color of dead cells as hex (overrides the theme)
+    #[arg(long, value_name = "HEX", value_parser = parse_hex_color)]
+    pub(crate) dead_color: Option<[u8; 3]>,
+
+    #[cfg(feature = "gui")]
+    /// Color of grid lines as hex (overrides the theme)
+    #[arg(long, value_name = "HEX", value_parser = parse_hex_color)]
+    pub(crate) grid_color: Option<[u8; 3]>,
+
+    #[cfg(feature = "gui")]
+    /// Draw grid lines in the GUI when cells are big enough (toggle with G)
+    #[arg(long, conflicts_with = "no_gui")]
+    pub(crate) grid: bool,
+
+    #[cfg(feature = "gui")]
+    /// File the GUI saves to with Ctrl+S (.rle, .txt or .png) and loads from with Ctrl+O [default: a new timestamped .rle file]
+    #[arg(long, value_name = "FILE", conflicts_with = "no_gui")]
+    pub(crate) save_path: Option<PathBuf>,
+
+    #[cfg(feature = "gui")]
+    /// Save the board (after --generations) as an image, e.g. board.png, at --scale pixels per cell instead of running
+    #[arg(long, value_name = "FILE")]
+    pub(crate) export: Option<PathBuf>,
+
     #[cfg(feature = "gui")]
     /// Disable GUI
     #[arg(long)]
     pub(crate) no_gui: bool,
 
+    #[cfg(feature = "tui")]
+    /// Run interactively in the terminal instead of the GUI, e.g. over SSH
+    #[arg(long)]
+    pub(crate) tui: bool,
+
+    /// How the terminal output draws cells: one character each, or packed into half blocks or Braille to fit the terminal
+    #[arg(long, value_enum, default_value_t = Render::Ascii)]
+    pub(crate) render: Render,
+
+    /// Color cells in terminal output with ANSI escapes
+    #[arg(long)]
+    pub(crate) ansi: bool,
+
     /// Updates per second (target)
     #[arg(short, long, default_value_t = 120)]
     pub(crate) ups: u64,
 }
 
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Search for a pattern which becomes the template after one generation
+    Predecessor {
+        /// The target board template, or the name of a built-in pattern
+        #[arg(short, long, value_parser = parse_template)]
+        template: Board,
+
+        /// Rule in B/S notation (e.g. B3/S23)
+        #[arg(short = 'R', long, value_parser = Rule::from_str)]
+        rule: Option<Rule>,
+    },
+    /// Browse the built-in patterns
+    Patterns {
+        #[command(subcommand)]
+        command: PatternsCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum PatternsCommand {
+    /// List the built-in patterns
+    List,
+    /// Describe a built-in pattern and print its board
+    Show {
+        #[arg(value_parser = PossibleValuesParser::new(PATTERNS.iter().map(|pattern| pattern.name)))]
+        name: String,
+    },
+}
+
 pub(crate) fn parse_args() -> Args {
     Args::parse()
 }
@@ -1,7 +1,9 @@
 extern crate test;
 
 use self::test::Bencher;
+use crate::bitwise::{Kernel, Scratch};
 use crate::board::Board;
+use crate::engine::{life_like, EngineKind};
 use assert_cmd::Command;
 
 #[bench]
@@ -20,6 +22,82 @@ fn bench_ten_generations(b: &mut Bencher) {
     });
 }
 
+/// Counting each cell's neighbors one by one, as the dense engines did before sliding a
+/// window along rows
+#[bench]
+fn bench_ten_generations_cell_by_cell(b: &mut Bencher) {
+    let mut brd = Board::new(200, 200).random();
+
+    b.iter(|| {
+        for _ in 0..10 {
+            let next = (0..brd.len())
+                .map(|cell| brd.successor_cell(None, cell))
+                .collect();
+            brd = brd.with_states(next);
+        }
+    });
+}
+
+#[bench]
+fn bench_ten_large_generations(b: &mut Bencher) {
+    let mut brd = Board::new(1000, 1000)
+        .random()
+        .with_engine(EngineKind::Serial.engine());
+
+    b.iter(|| {
+        for _ in 0..10 {
+            brd.step_in_place();
+        }
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn bench_ten_large_parallel_generations(b: &mut Bencher) {
+    let mut brd = Board::new(1000, 1000)
+        .random()
+        .with_engine(EngineKind::Rayon.engine());
+
+    b.iter(|| {
+        for _ in 0..10 {
+            brd.step_in_place();
+        }
+    });
+}
+
+fn bench_large_kernel_generation(b: &mut Bencher, kernel: Kernel) {
+    let brd = Board::new(1000, 1000).random();
+    let rule = life_like(brd.rule()).unwrap();
+    let (mut scratch, mut next) = (Scratch::default(), vec![0; brd.len()]);
+
+    b.iter(|| kernel.step(&mut scratch, &brd, rule, &mut next));
+}
+
+#[bench]
+fn bench_large_generation_scalar_bitwise(b: &mut Bencher) {
+    bench_large_kernel_generation(b, Kernel::Scalar);
+}
+
+/// With AVX2 or SSE2, whichever the CPU supports
+#[cfg(feature = "simd")]
+#[bench]
+fn bench_large_generation_simd_bitwise(b: &mut Bencher) {
+    bench_large_kernel_generation(b, Kernel::detect());
+}
+
+#[bench]
+fn bench_ten_generations_in_place(b: &mut Bencher) {
+    let mut brd = Board::new(200, 200)
+        .random()
+        .with_engine(EngineKind::Serial.engine());
+
+    b.iter(|| {
+        for _ in 0..10 {
+            brd.step_in_place();
+        }
+    });
+}
+
 #[cfg(feature = "rayon")]
 #[bench]
 fn bench_ten_parallel_generations(b: &mut Bencher) {
@@ -32,6 +110,41 @@ fn bench_ten_parallel_generations(b: &mut Bencher) {
     });
 }
 
+/// A 512×512 board, mostly empty but for a few random 64×64 soups, run until it has mostly
+/// settled into ash
+fn sparse_soup(engine: EngineKind) -> Board {
+    let mut brd = Board::new(512, 512).with_engine(engine.engine());
+    for (row, col) in [(40, 60), (300, 100), (200, 380), (420, 420)] {
+        brd.paste(&Board::new(64, 64).random(), row, col);
+    }
+    for _ in 0..300 {
+        brd.step_in_place();
+    }
+    brd
+}
+
+#[bench]
+fn bench_ten_sparse_soup_generations_full_scan(b: &mut Bencher) {
+    let mut brd = sparse_soup(EngineKind::Serial);
+
+    b.iter(|| {
+        for _ in 0..10 {
+            brd.step_in_place();
+        }
+    });
+}
+
+#[bench]
+fn bench_ten_sparse_soup_generations_tiled(b: &mut Bencher) {
+    let mut brd = sparse_soup(EngineKind::Tiled);
+
+    b.iter(|| {
+        for _ in 0..10 {
+            brd.step_in_place();
+        }
+    });
+}
+
 fn bin() -> Command {
     Command::cargo_bin("rust-life").unwrap()
 }
@@ -0,0 +1,417 @@
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use std::arch::x86_64::*;
+
+use crate::board::Board;
+
+/// Bitwise operations on a batch of packed words: a single `u64`, or several at once in a
+/// SIMD register
+trait Lanes: Copy {
+    /// How many words are processed at once
+    const WORDS: usize;
+
+    fn splat(word: u64) -> Self;
+    /// The first `WORDS` words of `words`
+    fn load(words: &[u64]) -> Self;
+    /// Write into the first `WORDS` words of `words`
+    fn store(self, words: &mut [u64]);
+    fn and(self, other: Self) -> Self;
+    fn or(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+
+    fn not(self) -> Self {
+        self.xor(Self::splat(!0))
+    }
+}
+
+impl Lanes for u64 {
+    const WORDS: usize = 1;
+
+    #[inline(always)]
+    fn splat(word: u64) -> Self {
+        word
+    }
+
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        words[0]
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        words[0] = self;
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        self & other
+    }
+
+    #[inline(always)]
+    fn or(self, other: Self) -> Self {
+        self | other
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+}
+
+// The SIMD lanes are only used inside `step_sse2` and `step_avx2`, which are only called once
+// the CPU is known to support their instructions; that is what makes the intrinsics sound.
+// Loads and stores go through slices, so they are bounds checked.
+
+/// Two words in an SSE2 register
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[derive(Copy, Clone)]
+struct Sse2(__m128i);
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+impl Lanes for Sse2 {
+    const WORDS: usize = 2;
+
+    #[inline(always)]
+    fn splat(word: u64) -> Self {
+        Sse2(unsafe { _mm_set1_epi64x(word as i64) })
+    }
+
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        Sse2(unsafe { _mm_loadu_si128(words[..Self::WORDS].as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        unsafe { _mm_storeu_si128(words[..Self::WORDS].as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        Sse2(unsafe { _mm_and_si128(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn or(self, other: Self) -> Self {
+        Sse2(unsafe { _mm_or_si128(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        Sse2(unsafe { _mm_xor_si128(self.0, other.0) })
+    }
+}
+
+/// Four words in an AVX2 register
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[derive(Copy, Clone)]
+struct Avx2(__m256i);
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+impl Lanes for Avx2 {
+    const WORDS: usize = 4;
+
+    #[inline(always)]
+    fn splat(word: u64) -> Self {
+        Avx2(unsafe { _mm256_set1_epi64x(word as i64) })
+    }
+
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        Avx2(unsafe { _mm256_loadu_si256(words[..Self::WORDS].as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        unsafe { _mm256_storeu_si256(words[..Self::WORDS].as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        Avx2(unsafe { _mm256_and_si256(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn or(self, other: Self) -> Self {
+        Avx2(unsafe { _mm256_or_si256(self.0, other.0) })
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        Avx2(unsafe { _mm256_xor_si256(self.0, other.0) })
+    }
+}
+
+/// A board's live cells with each row packed into 64-bit words, bit `c % 64` of word
+/// `c / 64` being column `c`, alongside the same rows shifted so each bit lines up with its
+/// west and east neighbors
+#[derive(Default)]
+struct Packed {
+    words: usize,
+    center: Vec<u64>,
+    west: Vec<u64>,
+    east: Vec<u64>,
+    /// A row of dead cells, for the rows beyond the edges
+    dead: Vec<u64>,
+}
+
+impl Packed {
+    /// Pack the cells of `brd`, reusing the buffers from the last board packed
+    fn pack(&mut self, brd: &Board) {
+        let (rows, cols) = (brd.rows(), brd.cols());
+        let words = cols.div_ceil(64);
+        self.words = words;
+        self.center.clear();
+        self.center.resize(rows * words, 0);
+        for (line, packed) in brd
+            .states()
+            .chunks(cols.max(1))
+            .zip(self.center.chunks_mut(words.max(1)))
+        {
+            for (cells, word) in line.chunks(64).zip(packed) {
+                *word = cells
+                    .iter()
+                    .rev()
+                    .fold(0, |word, &state| word << 1 | (state != 0) as u64);
+            }
+        }
+
+        self.west.resize(self.center.len(), 0);
+        self.east.resize(self.center.len(), 0);
+        for row in 0..rows {
+            let line = &self.center[row * words..(row + 1) * words];
+            for w in 0..words {
+                let (before, after) = (
+                    w.checked_sub(1).map_or(0, |w| line[w]),
+                    line.get(w + 1).copied().unwrap_or(0),
+                );
+                self.west[row * words + w] = line[w] << 1 | before >> 63;
+                self.east[row * words + w] = line[w] >> 1 | after << 63;
+            }
+        }
+
+        self.dead.clear();
+        self.dead.resize(words, 0);
+    }
+
+    /// The west, center and east planes of `row`, or dead ones beyond the edges
+    fn planes(&self, row: Option<usize>) -> [&[u64]; 3] {
+        let rows = self.center.len() / self.words.max(1);
+        match row.filter(|&row| row < rows) {
+            Some(row) => {
+                let line = row * self.words..(row + 1) * self.words;
+                [
+                    &self.west[line.clone()],
+                    &self.center[line.clone()],
+                    &self.east[line],
+                ]
+            }
+            None => [&self.dead; 3],
+        }
+    }
+}
+
+/// The next states of words `w..w + L::WORDS` of a row, given the planes of the rows above,
+/// at and below it, found by adding up the eight neighbor planes with a bit-sliced adder and
+/// matching the counts against the rule
+#[inline(always)]
+fn step_lanes<L: Lanes>(planes: &[[&[u64]; 3]; 3], (born, survive): (u16, u16), w: usize) -> L {
+    let [above, current, below] = planes.map(|planes| planes.map(|plane| L::load(&plane[w..])));
+    let alive = current[1];
+
+    // Bit `c` of `count[i]` is bit `i` of cell `c`'s neighbor count
+    let mut count = [L::splat(0); 4];
+    for plane in above
+        .into_iter()
+        .chain([current[0], current[2]])
+        .chain(below)
+    {
+        let mut carry = plane;
+        for bit in &mut count {
+            let next = bit.and(carry);
+            *bit = bit.xor(carry);
+            carry = next;
+        }
+    }
+
+    let mut cells = L::splat(0);
+    for n in 0..=8 {
+        let cells_for = match (born >> n & 1 == 1, survive >> n & 1 == 1) {
+            (false, false) => continue,
+            (true, false) => alive.not(),
+            (false, true) => alive,
+            (true, true) => L::splat(!0),
+        };
+        let equal = count
+            .iter()
+            .enumerate()
+            .fold(L::splat(!0), |equal, (i, &bit)| {
+                equal.and(if n >> i & 1 == 1 { bit } else { bit.not() })
+            });
+        cells = cells.or(equal.and(cells_for));
+    }
+    cells
+}
+
+/// The next generation of every word, `L::WORDS` at a time with single words for the rest
+#[inline(always)]
+fn step_words<L: Lanes>(packed: &Packed, rule: (u16, u16), out: &mut [u64]) {
+    let words = packed.words;
+    let whole = words - words % L::WORDS;
+
+    for (row, line) in out.chunks_mut(words.max(1)).enumerate() {
+        let planes = [row.checked_sub(1), Some(row), Some(row + 1)].map(|row| packed.planes(row));
+
+        for w in (0..whole).step_by(L::WORDS) {
+            step_lanes::<L>(&planes, rule, w).store(&mut line[w..]);
+        }
+        for (w, word) in line.iter_mut().enumerate().skip(whole) {
+            *word = step_lanes::<u64>(&planes, rule, w);
+        }
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn step_sse2(packed: &Packed, rule: (u16, u16), out: &mut [u64]) {
+    step_words::<Sse2>(packed, rule, out)
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn step_avx2(packed: &Packed, rule: (u16, u16), out: &mut [u64]) {
+    step_words::<Avx2>(packed, rule, out)
+}
+
+/// The buffers a kernel packs a board into and steps it with, kept to be reused
+#[derive(Default)]
+pub(crate) struct Scratch {
+    packed: Packed,
+    /// The packed next generation
+    out: Vec<u64>,
+}
+
+/// Which instructions step the packed words
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Kernel {
+    /// One word at a time, on any CPU
+    Scalar,
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    Sse2,
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    Avx2,
+}
+
+impl Kernel {
+    /// The fastest kernel this CPU supports
+    pub(crate) fn detect() -> Kernel {
+        Kernel::supported()
+            .last()
+            .expect("the scalar kernel is always supported")
+    }
+
+    /// Every kernel this CPU supports, slowest first
+    pub(crate) fn supported() -> impl Iterator<Item = Kernel> {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        let simd = [
+            (Kernel::Sse2, is_x86_feature_detected!("sse2")),
+            (Kernel::Avx2, is_x86_feature_detected!("avx2")),
+        ];
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        let simd = [];
+
+        [(Kernel::Scalar, true)]
+            .into_iter()
+            .chain(simd)
+            .filter_map(|(kernel, supported)| supported.then_some(kernel))
+    }
+
+    /// Write the next generation of `brd`, running a life-like rule given as born and survive
+    /// masks, into `next`, in the buffers of `scratch`. Panics if the CPU doesn't support the
+    /// kernel.
+    pub(crate) fn step(
+        self,
+        scratch: &mut Scratch,
+        brd: &Board,
+        rule: (u16, u16),
+        next: &mut [u8],
+    ) {
+        let Scratch { packed, out } = scratch;
+        packed.pack(brd);
+        out.resize(packed.center.len(), 0);
+
+        match self {
+            Kernel::Scalar => step_words::<u64>(packed, rule, out),
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            Kernel::Sse2 => {
+                assert!(is_x86_feature_detected!("sse2"));
+                // SAFETY: the CPU supports SSE2
+                unsafe { step_sse2(packed, rule, out) }
+            }
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            Kernel::Avx2 => {
+                assert!(is_x86_feature_detected!("avx2"));
+                // SAFETY: the CPU supports AVX2
+                unsafe { step_avx2(packed, rule, out) }
+            }
+        }
+
+        let cols = brd.cols().max(1);
+        for (line, packed) in next.chunks_mut(cols).zip(out.chunks(packed.words.max(1))) {
+            for (cells, &word) in line.chunks_mut(64).zip(packed) {
+                for (bit, cell) in cells.iter_mut().enumerate() {
+                    *cell = (word >> bit & 1) as u8;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_kernels() {
+    use crate::rule::Rule;
+    use std::str::FromStr;
+
+    let rules = ["B3/S23", "B36/S23", "B0/S8", "B2/S", "B1/S012345678"];
+    // Widths either side of whole words and whole SIMD registers
+    let sizes = [
+        (0, 0),
+        (1, 1),
+        (3, 63),
+        (4, 64),
+        (5, 65),
+        (3, 128),
+        (6, 200),
+        (2, 257),
+    ];
+
+    for kernel in Kernel::supported() {
+        // Shared by every size, as an engine's is
+        let mut scratch = Scratch::default();
+        for rule in rules {
+            let rule = Rule::from_str(rule).unwrap();
+            let masks = crate::engine::life_like(&rule).unwrap();
+            for (rows, cols) in sizes {
+                let mut brd = Board::new_with_rule(rows, cols, rule.clone()).random();
+                for _ in 0..10 {
+                    let mut next = vec![0; brd.len()];
+                    kernel.step(&mut scratch, &brd, masks, &mut next);
+                    let expected = brd.serial_next_generation();
+
+                    assert_eq!(next, expected.states(), "{kernel:?}, {rule}, {rows}x{cols}");
+                    brd = expected;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_detect() {
+    assert!(Kernel::supported().any(|kernel| kernel == Kernel::detect()));
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    assert_ne!(
+        Kernel::detect(),
+        Kernel::Scalar,
+        "every x86_64 CPU has SSE2"
+    );
+}
@@ -1,6 +1,9 @@
-use rand::{distr::StandardUniform, rng, Rng};
+use crate::builder::BoardBuilder;
 #[cfg(feature = "rayon")]
-use rayon::prelude::*;
+use crate::engine::Rayon;
+use crate::engine::{Engine, EngineKind, Serial};
+use crate::rule::{PrefixSums, Rule};
+use rand::{distr::StandardUniform, rng, Rng};
 use std::cmp::max;
 use std::error::Error;
 use std::fmt;
@@ -10,134 +13,277 @@ use std::sync::Arc;
 
 const LIVE_CELL: char = '@';
 const DEAD_CELL: char = '.';
-
-#[derive(PartialEq, Eq, Clone, Debug)]
+/// States above 1 are written as letters, as in Golly's multi-state RLE (`A` is state 1)
+const STATE_LETTERS: u8 = b'A' - 1;
+/// States past `X` (24) run on through the accented letters from `Ā`, one character each
+const EXTENDED_LETTERS: u32 = 'Ā' as u32 - 25;
+
+/// A finite grid of cells, each in a state from 0 (dead) up to its rule's number of states,
+/// along with the rule and how many generations it has been run for. Cells beyond the edges
+/// count as dead.
+///
+/// Boards parse from and display as text with one character per cell: `@` for live, `.` for
+/// dead and `A`, `B`, ... for the states of multi-state rules, continuing with `Ā`, `ā`, ...
+/// after `X`.
+///
+/// Each board also carries the [`Engine`] that computes its generations, which is passed on
+/// to the boards made from it. Boards compare equal whatever their engines.
 pub struct Board {
-    board: Vec<bool>,
-    survive: Arc<Vec<usize>>,
-    born: Arc<Vec<usize>>,
+    board: Vec<u8>,
+    /// Where [`Board::step_in_place`] computes the next generation before swapping it with
+    /// `board`, kept to be reused
+    scratch: Vec<u8>,
+    rule: Arc<Rule>,
+    engine: Arc<dyn Engine>,
     rows: usize,
     cols: usize,
     generation: usize,
 }
 
+impl Clone for Board {
+    fn clone(&self) -> Board {
+        Board {
+            board: self.board.clone(),
+            scratch: vec![],
+            rule: Arc::clone(&self.rule),
+            engine: Arc::clone(&self.engine),
+            ..*self
+        }
+    }
+
+    /// Copies the cells into the existing buffer, so it can be reused
+    fn clone_from(&mut self, source: &Board) {
+        self.board.clone_from(&source.board);
+        self.rule = Arc::clone(&source.rule);
+        self.engine = Arc::clone(&source.engine);
+        (self.rows, self.cols, self.generation) = (source.rows, source.cols, source.generation);
+    }
+}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Board")
+            .field("board", &self.board)
+            .field("rule", &self.rule)
+            .field("engine", &self.engine)
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        (
+            &self.board,
+            &self.rule,
+            self.rows,
+            self.cols,
+            self.generation,
+        ) == (
+            &other.board,
+            &other.rule,
+            other.rows,
+            other.cols,
+            other.generation,
+        )
+    }
+}
+
+impl Eq for Board {}
+
 impl Board {
-    pub fn new(rows: usize, cols: usize) -> Board {
-        let born = vec![3];
-        let survive = vec![2, 3];
+    /// Set up a board step by step
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder::new()
+    }
 
-        Board::new_with_custom_rules(rows, cols, born, survive)
+    /// An empty board running B3/S23 (Conway's Life)
+    pub fn new(rows: usize, cols: usize) -> Board {
+        Board::new_with_rule(rows, cols, Rule::default())
     }
 
-    fn new_with_custom_rules(
-        rows: usize,
-        cols: usize,
-        born: Vec<usize>,
-        survive: Vec<usize>,
-    ) -> Board {
-        let new_board = repeat_n(false, rows * cols).collect();
+    /// An empty board running `rule`
+    pub fn new_with_rule(rows: usize, cols: usize, rule: Rule) -> Board {
+        let new_board = repeat_n(0, rows * cols).collect();
 
         Board {
             board: new_board,
-            born: Arc::new(born),
-            survive: Arc::new(survive),
+            scratch: vec![],
+            rule: Arc::new(rule),
+            engine: EngineKind::default().engine(),
             rows,
             cols,
             generation: 0,
         }
     }
 
+    /// The number of rows of cells
     pub fn rows(&self) -> usize {
         self.rows
     }
 
+    /// The number of columns of cells
     pub fn cols(&self) -> usize {
         self.cols
     }
 
+    /// How many generations the board has been advanced
     pub fn generation(&self) -> usize {
         self.generation
     }
 
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// The same cells running another rule
+    pub fn with_rule(&self, rule: Rule) -> Board {
+        Board {
+            rule: Arc::new(rule),
+            ..self.clone()
+        }
+    }
+
+    /// The engine computing the board's generations
+    pub fn engine(&self) -> &Arc<dyn Engine> {
+        &self.engine
+    }
+
+    /// The same board, computing its generations with `engine`
+    pub fn with_engine(&self, engine: Arc<dyn Engine>) -> Board {
+        Board {
+            engine,
+            ..self.clone()
+        }
+    }
+
+    /// The number of cells
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.rows * self.cols
     }
 
-    #[allow(dead_code)]
+    /// The number of live cells
     pub fn population(&self) -> usize {
-        self.iter().filter(|&&x| x).count()
+        self.iter().filter(|&x| x).count()
     }
 
-    fn next_board(&self, new_board: Vec<bool>) -> Board {
+    fn next_board(&self, new_board: Vec<u8>) -> Board {
         assert_eq!(new_board.len(), self.len());
 
         self.resized_next_board(new_board, self.rows, self.cols)
     }
 
-    fn resized_next_board(&self, new_board: Vec<bool>, rows: usize, cols: usize) -> Board {
+    fn resized_next_board(&self, new_board: Vec<u8>, rows: usize, cols: usize) -> Board {
         assert_eq!(new_board.len(), rows * cols);
 
         Board {
             board: new_board,
-            born: Arc::clone(&self.born),
-            survive: Arc::clone(&self.survive),
+            scratch: vec![],
+            rule: Arc::clone(&self.rule),
+            engine: Arc::clone(&self.engine),
             rows,
             cols,
             generation: self.generation,
         }
     }
 
-    fn next_generation_board(&self, new_board: Vec<bool>) -> Board {
+    /// A board with the same size, rule and generation, but the given cell states
+    pub(crate) fn with_states(&self, states: Vec<u8>) -> Board {
+        self.next_board(states)
+    }
+
+    /// The same board, counted as being at `generation`
+    pub(crate) fn with_generation(&self, generation: usize) -> Board {
+        Board {
+            generation,
+            ..self.clone()
+        }
+    }
+
+    fn next_generation_board(&self, new_board: Vec<u8>) -> Board {
         Board {
             generation: self.generation + 1,
             ..self.next_board(new_board)
         }
     }
 
+    /// A board of the same size with every cell in a random state
     pub fn random(&self) -> Board {
-        let brd = rng()
-            .sample_iter(&StandardUniform)
-            .take(self.len())
-            .collect();
+        let brd = match self.rule.states() {
+            2 => rng()
+                .sample_iter::<bool, _>(&StandardUniform)
+                .take(self.len())
+                .map(u8::from)
+                .collect(),
+            states => {
+                let mut rng = rng();
+                (0..self.len())
+                    .map(|_| rng.random_range(0..states))
+                    .collect()
+            }
+        };
 
         self.next_board(brd)
     }
 
-    #[allow(dead_code)]
-    pub fn serial_next_generation(&self) -> Board {
-        let new_brd = (0..self.len())
-            .map(|cell| self.successor_cell(cell))
-            .collect();
+    fn next_generation_with(&self, engine: &dyn Engine) -> Board {
+        let mut next = vec![0; self.len()];
+        engine.step(self, &mut next);
 
-        self.next_generation_board(new_brd)
+        self.next_generation_board(next)
     }
 
+    /// The next generation as a new board, computed by the board's engine
     pub fn next_generation(&self) -> Board {
-        #[cfg(feature = "rayon")]
-        let next = self.parallel_next_generation();
-        #[cfg(not(feature = "rayon"))]
-        let next = self.serial_next_generation();
-        next
+        self.next_generation_with(&*self.engine)
+    }
+
+    /// The next generation, computed on this thread whatever the board's engine
+    pub fn serial_next_generation(&self) -> Board {
+        self.next_generation_with(&Serial)
     }
 
+    /// The next generation, computed across a thread pool whatever the board's engine
     #[cfg(feature = "rayon")]
     pub fn parallel_next_generation(&self) -> Board {
-        let new_brd = (0..self.len())
-            .into_par_iter()
-            .map(|cell| self.successor_cell(cell))
-            .collect();
-
-        self.next_generation_board(new_brd)
+        self.next_generation_with(&Rayon)
     }
 
-    #[cfg(not(feature = "rayon"))]
-    pub fn parallel_next_generation(&self) -> Board {
-        unimplemented!("Need 'rayon' feature for parallelism")
+    /// Advance this board to its next generation, without allocating once it has been stepped
+    /// before: the board's engine writes into a second buffer kept alongside the cells, and the
+    /// two are swapped. Use [`Board::next_generation`] to keep the current board as well.
+    ///
+    /// ```
+    /// use rust_life::Board;
+    ///
+    /// let mut blinker: Board = "...\n@@@\n...".parse().unwrap();
+    /// blinker.step_in_place();
+    ///
+    /// assert_eq!(blinker.to_string(), ".@.\n.@.\n.@.");
+    /// assert_eq!(blinker.generation(), 1);
+    /// ```
+    pub fn step_in_place(&mut self) {
+        let mut next = std::mem::take(&mut self.scratch);
+        next.resize(self.len(), 0);
+        self.engine.step(self, &mut next);
+
+        self.scratch = std::mem::replace(&mut self.board, next);
+        self.generation += 1;
     }
 
     fn cell_live(&self, x: usize, y: usize) -> bool {
-        !(x >= self.cols || y >= self.rows) && self.board[y * self.cols + x]
+        self.cell_state(x, y) != 0
+    }
+
+    fn cell_state(&self, x: usize, y: usize) -> u8 {
+        if x >= self.cols || y >= self.rows {
+            0
+        } else {
+            self.board[y * self.cols + x]
+        }
     }
 
     fn living_neighbors(&self, x: usize, y: usize) -> usize {
@@ -151,35 +297,97 @@ impl Board {
         neighbors.iter().filter(|&x| *x).count()
     }
 
-    fn successor_cell(&self, cell: usize) -> bool {
-        self.successor(cell % self.cols, cell / self.cols)
+    /// Larger than Life neighborhoods are counted from a summed-area table built once per generation
+    pub(crate) fn prefix_sums(&self) -> Option<PrefixSums> {
+        match *self.rule {
+            Rule::LargerThanLife(_) => Some(PrefixSums::new(self.iter(), self.rows, self.cols)),
+            Rule::LifeLike { .. } | Rule::Table(_) => None,
+        }
     }
 
-    fn successor(&self, x: usize, y: usize) -> bool {
-        let neighbors = self.living_neighbors(x, y);
-        if self.cell_live(x, y) {
-            self.survive.contains(&neighbors)
-        } else {
-            self.born.contains(&neighbors)
-        }
+    pub(crate) fn successor_cell(&self, sums: Option<&PrefixSums>, cell: usize) -> u8 {
+        self.successor(sums, cell % self.cols, cell / self.cols)
+    }
+
+    fn successor(&self, sums: Option<&PrefixSums>, x: usize, y: usize) -> u8 {
+        let alive = self.cell_live(x, y);
+
+        let next = match (&*self.rule, sums) {
+            (Rule::LifeLike { born, survive }, _) => {
+                let neighbors = self.living_neighbors(x, y);
+                if alive {
+                    survive.contains(&neighbors)
+                } else {
+                    born.contains(&neighbors)
+                }
+            }
+            (Rule::LargerThanLife(rule), Some(sums)) => {
+                rule.successor(alive, sums.count(rule, x, y, alive))
+            }
+            (Rule::LargerThanLife(_), None) => unreachable!("missing prefix sums"),
+            (Rule::Table(table), _) => {
+                return table.successor(table.offsets().iter().map(|&(dx, dy)| {
+                    self.cell_state(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+                }))
+            }
+        };
+
+        next as u8
     }
 
+    /// Advance the cell to its next state, wrapping back to dead after the last one
     pub fn toggle(&self, x: usize, y: usize) -> Board {
         if x < self.rows && y < self.cols {
-            let mut board = self.board.clone();
-            board[x * self.cols + y] = !board[x * self.cols + y];
-            self.next_board(board)
+            let mut brd = self.clone();
+            brd.set(x, y, self.next_state(self.cell(x, y)));
+            brd
         } else {
             self.clone()
         }
     }
 
+    /// The state a cell in `state` becomes when toggled
+    pub fn next_state(&self, state: u8) -> u8 {
+        match state.checked_add(1) {
+            Some(next) if next < self.rule.states() => next,
+            _ => 0,
+        }
+    }
+
+    /// The state of the cell at `row`, `col` (dead if outside the board)
+    pub fn cell(&self, row: usize, col: usize) -> u8 {
+        self.cell_state(col, row)
+    }
+
+    /// Set a single cell in place; coordinates outside the board are ignored
+    pub fn set(&mut self, row: usize, col: usize, state: u8) {
+        if row < self.rows && col < self.cols {
+            self.board[row * self.cols + col] = state;
+        }
+    }
+
+    /// The smallest `(left, top, right, bottom)` box (inclusive) containing every live cell
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let live = |i: &usize| self.board[*i] != 0;
+        let (first, last) = ((0..self.len()).find(live)?, (0..self.len()).rfind(live)?);
+        let (left, right) = (0..self.cols)
+            .filter(|&x| (0..self.rows).any(|y| self.board[y * self.cols + x] != 0))
+            .fold((usize::MAX, 0), |(min, max), x| (min.min(x), max.max(x)));
+
+        Some((left, first / self.cols, right, last / self.cols))
+    }
+
+    /// An empty board of the same size and rule, back at generation 0
     pub fn clear(&self) -> Board {
-        Board::new(self.rows, self.cols)
+        Board {
+            generation: 0,
+            ..self.next_board(repeat_n(0, self.len()).collect())
+        }
     }
 
+    /// Add dead cells around the edges (or remove rows and columns, for negative padding)
     pub fn pad(&self, top: isize, right: isize, bottom: isize, left: isize) -> Board {
-        let new_cell_values = repeat(false);
+        let new_cell_values = repeat(0);
         let (rows, cols) = (
             max(0, top + self.rows as isize + bottom) as usize,
             max(0, left + self.cols as isize + right) as usize,
@@ -215,17 +423,157 @@ impl Board {
         self.resized_next_board(dst_cells, rows, cols)
     }
 
-    pub fn iter(&self) -> std::slice::Iter<bool> {
-        self.board.iter()
+    /// The cells from `(left, top)` to `(right, bottom)` inclusive, as a new board (empty if
+    /// this one is)
+    pub fn crop(&self, left: usize, top: usize, right: usize, bottom: usize) -> Board {
+        if self.rows == 0 || self.cols == 0 {
+            return self.pad(0, -(self.cols as isize), -(self.rows as isize), 0);
+        }
+        let (right, bottom) = (right.min(self.cols - 1), bottom.min(self.rows - 1));
+
+        self.pad(
+            -(top as isize),
+            right as isize + 1 - self.cols as isize,
+            bottom as isize + 1 - self.rows as isize,
+            -(left as isize),
+        )
+    }
+
+    /// Copy every cell of `pattern` onto this board with its top-left corner at `row`, `col`;
+    /// cells falling outside the board are dropped
+    pub fn paste(&mut self, pattern: &Board, row: isize, col: isize) {
+        for (i, &state) in pattern.board.iter().enumerate() {
+            let (r, c) = (
+                row + (i / pattern.cols) as isize,
+                col + (i % pattern.cols) as isize,
+            );
+            if r >= 0 && c >= 0 {
+                self.set(r as usize, c as usize, state);
+            }
+        }
+    }
+
+    /// A quarter turn clockwise
+    pub fn rotate_clockwise(&self) -> Board {
+        let cells = (0..self.cols)
+            .flat_map(|col| (0..self.rows).rev().map(move |row| (row, col)))
+            .map(|(row, col)| self.board[row * self.cols + col])
+            .collect();
+
+        self.resized_next_board(cells, self.cols, self.rows)
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Board {
+        let cells = self
+            .board
+            .chunks(self.cols.max(1))
+            .flat_map(|row| row.iter().rev().copied())
+            .collect();
+
+        self.next_board(cells)
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Board {
+        let cells = self
+            .board
+            .chunks(self.cols.max(1))
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+
+        self.next_board(cells)
+    }
+
+    /// Whether each cell is live (in any non-dead state), row by row
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.board.iter().map(|&state| state != 0)
+    }
+
+    /// The state of each cell, row by row
+    pub fn states(&self) -> &[u8] {
+        &self.board
+    }
+
+    /// The board followed by each of its generations in turn, computed as they are needed.
+    /// [`Iterator::step_by`] skips over generations without copying them.
+    ///
+    /// ```
+    /// use rust_life::Board;
+    ///
+    /// let pre_block: Board = "@@.\n@..\n...".parse().unwrap();
+    /// let block = pre_block.generations().find(|brd| brd.population() == 4).unwrap();
+    /// let every_tenth: Vec<usize> = pre_block
+    ///     .generations()
+    ///     .step_by(10)
+    ///     .take(3)
+    ///     .map(|brd| brd.generation())
+    ///     .collect();
+    ///
+    /// assert_eq!(block.generation(), 1);
+    /// assert_eq!(every_tenth, [0, 10, 20]);
+    /// ```
+    pub fn generations(&self) -> Generations {
+        Generations {
+            brd: self.clone(),
+            started: false,
+        }
+    }
+}
+
+/// An endless iterator over a board's generations, from [`Board::generations`]
+#[derive(Clone, Debug)]
+pub struct Generations {
+    brd: Board,
+    /// Whether `brd` has been yielded, and so must be advanced first next time
+    started: bool,
+}
+
+impl Generations {
+    fn advance(&mut self) {
+        if self.started {
+            self.brd.step_in_place();
+        }
+        self.started = true;
+    }
+}
+
+impl Iterator for Generations {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        self.advance();
+        Some(self.brd.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Board> {
+        for _ in 0..n {
+            self.advance();
+        }
+        self.next()
+    }
+}
+
+/// The character a cell state is written as
+pub(crate) fn state_char(state: u8) -> char {
+    match state {
+        0 => DEAD_CELL,
+        1 => LIVE_CELL,
+        2..=24 => (STATE_LETTERS + state) as char,
+        n => char::from_u32(EXTENDED_LETTERS + n as u32).unwrap_or(DEAD_CELL),
     }
 }
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fn row_to_str(row: &[bool]) -> String {
-            row.iter()
-                .map(|&cell| if cell { LIVE_CELL } else { DEAD_CELL })
-                .collect()
+        fn row_to_str(row: &[u8]) -> String {
+            row.iter().map(|&cell| state_char(cell)).collect()
         }
 
         let rows: Vec<String> = self.board.chunks(self.cols).map(row_to_str).collect();
@@ -234,8 +582,9 @@ impl fmt::Display for Board {
     }
 }
 
+/// Why text couldn't be parsed as a board
 #[derive(Debug, PartialEq)]
-pub struct ParseBoardErr(String);
+pub struct ParseBoardErr(pub(crate) String);
 
 impl Error for ParseBoardErr {}
 
@@ -253,19 +602,22 @@ impl FromStr for Board {
             .split_terminator('\n')
             .filter(|row| !row.is_empty())
             .collect();
-        let (row_cnt, col_cnt) = (rows.len(), rows.first().map_or(0, |x| x.len()));
+        let width = |row: &&str| row.chars().count();
+        let (row_cnt, col_cnt) = (rows.len(), rows.first().map_or(0, width));
 
-        if rows.iter().any(|x| x.len() != col_cnt) {
+        if rows.iter().any(|row| width(row) != col_cnt) {
             return Err(ParseBoardErr("row length mismatch".into()));
         };
 
         let chars: String = rows.concat();
 
-        let brd: Result<Vec<bool>, ParseBoardErr> = chars
+        let brd: Result<Vec<u8>, ParseBoardErr> = chars
             .chars()
             .map(|c| match c {
-                LIVE_CELL => Ok(true),
-                DEAD_CELL => Ok(false),
+                LIVE_CELL => Ok(1),
+                DEAD_CELL => Ok(0),
+                'A'..='X' => Ok(c as u8 - STATE_LETTERS),
+                'Ā'..='Ǧ' => Ok((c as u32 - EXTENDED_LETTERS) as u8),
                 c => Err(ParseBoardErr(format!("Unexpected '{c}'"))),
             })
             .collect();
@@ -276,7 +628,7 @@ impl FromStr for Board {
 
 #[cfg(test)]
 #[rustfmt::skip]
-const TEST_BOARDS: [&'static str; 9] = [
+const TEST_BOARDS: [&str; 9] = [
     ".@.\n.@@\n.@@",
     "...\n@@@\n...",
     ".@.\n.@.\n.@.",
@@ -350,6 +702,44 @@ fn test_next_generation() {
     assert_eq!(testing_board(1).next_generation().generation(), 1);
 }
 
+#[test]
+fn test_generations() {
+    let brd = testing_board(1);
+    let mut generations = brd.generations();
+
+    assert_eq!(generations.next(), Some(brd.clone()));
+    assert_eq!(generations.next(), Some(testing_board_generation(2, 1)));
+    assert_eq!(generations.nth(1).map(|brd| brd.generation()), Some(3));
+
+    let stepped: Vec<_> = brd.generations().step_by(4).take(3).collect();
+    let every: Vec<_> = brd.generations().take(9).collect();
+    assert_eq!(
+        stepped,
+        [every[0].clone(), every[4].clone(), every[8].clone()]
+    );
+}
+
+#[test]
+fn test_step_in_place() {
+    let mut brd = Board::new(20, 20).random();
+    let expected = brd.next_generation().next_generation().next_generation();
+
+    brd.step_in_place();
+    let buffers = (brd.board.as_ptr(), brd.scratch.as_ptr());
+    brd.step_in_place();
+    brd.step_in_place();
+
+    assert_eq!(brd, expected);
+    // The two buffers are swapped back and forth rather than reallocated
+    assert_eq!((brd.board.as_ptr(), brd.scratch.as_ptr()), buffers);
+
+    let mut copy = Board::new(20, 20);
+    let buffer = copy.board.as_ptr();
+    copy.clone_from(&brd);
+    assert_eq!(copy, brd);
+    assert_eq!(copy.board.as_ptr(), buffer);
+}
+
 #[test]
 fn test_serial_next_generation() {
     assert_eq!(
@@ -371,11 +761,13 @@ fn test_parallel_next_generation() {
     assert_eq!(testing_board(1).parallel_next_generation().generation(), 1);
 }
 
-#[cfg(not(feature = "rayon"))]
 #[test]
-#[should_panic]
-fn test_parallel_next_generation() {
-    testing_board(1).parallel_next_generation();
+fn test_engine() {
+    let brd = testing_board(1).with_engine(EngineKind::Hashlife.engine());
+
+    assert_eq!(brd, testing_board(1));
+    assert_eq!(brd.next_generation(), testing_board_generation(2, 1));
+    assert!(Arc::ptr_eq(brd.next_generation().engine(), brd.engine()));
 }
 
 #[test]
@@ -403,6 +795,130 @@ fn test_toggle() {
     assert_eq!(brd.toggle(0, 999), brd);
 }
 
+#[test]
+fn test_clear_keeps_rule() {
+    let rule = Rule::from_str("B36/S23").unwrap();
+    let brd = testing_board(0).with_rule(rule.clone());
+
+    assert_eq!(brd.clear().rule(), &rule);
+}
+
+#[test]
+fn test_ltl_next_generation() {
+    // With radius 1, Moore neighborhood and no middle cell, LtL reduces to the Life-like rule
+    let ltl = Rule::from_str("R1,C0,M0,S2..3,B3..3,NM").unwrap();
+    let brd = Board::new(20, 20).random();
+    let (mut life, mut ltl) = (brd.clone(), brd.with_rule(ltl));
+
+    for _ in 0..5 {
+        life = life.next_generation();
+        ltl = ltl.serial_next_generation();
+        assert!(life.iter().eq(ltl.iter()));
+    }
+}
+
+#[test]
+fn test_ltl_bosco() {
+    let bosco = Rule::from_str("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    // A lone cell can't survive with only itself in range
+    let brd = testing_board(4).with_rule(bosco.clone());
+    assert_eq!(brd.next_generation().population(), 0);
+
+    // A solid 11×11 block: corners see 36 live cells in range (survive), center sees all 121 (dies)
+    let block = Board::new(11, 11).with_rule(bosco);
+    let block = block.next_board(vec![1; 121]);
+    let next = block.next_generation();
+    assert!(next.cell_live(0, 0));
+    assert!(!next.cell_live(5, 5));
+    assert_eq!(next, block.serial_next_generation());
+}
+
+#[test]
+fn test_multistate_str_conversion() {
+    let brd = Board::from_str(".@B\nCAX").unwrap();
+
+    assert_eq!(brd.states(), &[0, 1, 2, 3, 1, 24]);
+    assert_eq!(brd.to_string(), ".@B\nC@X");
+    assert_eq!(brd.population(), 5);
+
+    let extended = Board::new(1, 4).with_states(vec![25, 30, 100, 255]);
+    assert_eq!(extended.to_string(), "ĀąŋǦ");
+    assert_eq!(Board::from_str(&extended.to_string()).unwrap(), extended);
+    assert!(Board::from_str("Ǧ.\nǧ.").is_err());
+}
+
+#[test]
+fn test_wireworld() {
+    use crate::rule_table::{RuleTable, WIREWORLD};
+
+    let wireworld = Rule::Table(RuleTable::parse(WIREWORLD).unwrap());
+    // An electron travelling east along a wire
+    let brd = Board::from_str("CBACC").unwrap().with_rule(wireworld);
+
+    assert_eq!(brd.next_generation().to_string(), "CCB@C");
+    assert_eq!(brd.serial_next_generation().to_string(), "CCB@C");
+    assert_eq!(brd.next_generation().next_generation().to_string(), "CCCB@");
+    assert_eq!(brd.toggle(0, 0).to_string(), ".B@CC");
+    assert_eq!(brd.toggle(0, 0).toggle(0, 0).to_string(), "@B@CC");
+    let random = Board::new_with_rule(10, 10, brd.rule().clone()).random();
+    assert!(random.states().iter().all(|&state| state < 4));
+    assert!(random.states().iter().any(|&state| state > 1));
+}
+
+#[test]
+fn test_set() {
+    let mut brd = testing_board(4);
+
+    brd.set(0, 2, 1);
+    brd.set(1, 1, 0);
+    brd.set(3, 0, 1);
+    brd.set(0, 3, 1);
+
+    assert_eq!(brd.to_string(), "..@\n...\n...");
+    assert_eq!(brd.cell(0, 2), 1);
+    assert_eq!(brd.cell(9, 9), 0);
+}
+
+#[test]
+fn test_crop_and_paste() {
+    let brd = testing_board(0);
+    let cropped = brd.crop(1, 1, 2, 5);
+
+    assert_eq!(cropped.to_string(), "@@\n@@");
+    for empty in [Board::new(0, 0), Board::new(0, 4), Board::new(4, 0)] {
+        let cropped = empty.crop(0, 0, 2, 2);
+        assert_eq!((cropped.rows(), cropped.cols()), (0, 0));
+    }
+
+    let mut pasted = Board::new(3, 3);
+    pasted.paste(&cropped, -1, 2);
+    assert_eq!(pasted.to_string(), "..@\n...\n...");
+}
+
+#[test]
+fn test_transforms() {
+    let brd = Board::from_str("@@.\n..@").unwrap();
+
+    assert_eq!(brd.rotate_clockwise().to_string(), ".@\n.@\n@.");
+    assert_eq!(brd.flip_horizontal().to_string(), ".@@\n@..");
+    assert_eq!(brd.flip_vertical().to_string(), "..@\n@@.");
+    assert_eq!(
+        brd.rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise(),
+        brd
+    );
+}
+
+#[test]
+fn test_bounding_box() {
+    assert_eq!(testing_board(0).bounding_box(), Some((1, 0, 2, 2)));
+    assert_eq!(testing_board(1).bounding_box(), Some((0, 1, 2, 1)));
+    assert_eq!(testing_board(4).bounding_box(), Some((1, 1, 1, 1)));
+    assert_eq!(Board::new(3, 3).bounding_box(), None);
+}
+
 #[test]
 fn test_pad() {
     assert_eq!(testing_board(3).pad(1, 1, 1, 1), testing_board(4));
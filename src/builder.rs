@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use clap::ValueEnum;
+
+use crate::board::Board;
+use crate::engine::Engine;
+use crate::rule::Rule;
+
+/// Where a template is placed within a larger board
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+#[rustfmt::skip]
+pub enum Alignment {
+    TopLeft   , Top   , TopRight   ,
+    Left      , Center, Right      ,
+    BottomLeft, Bottom, BottomRight,
+}
+
+/// Builds a [`Board`]: empty or random at a given size, or a template placed within one,
+/// optionally with a rule and advanced some generations
+///
+/// ```
+/// use rust_life::{Alignment, Board};
+///
+/// let glider: Board = ".@.\n..@\n@@@".parse().unwrap();
+/// let brd = Board::builder()
+///     .size(8, 8)
+///     .template(glider)
+///     .align(Alignment::TopLeft)
+///     .rule("B36/S23".parse().unwrap())
+///     .generations(4)
+///     .build();
+///
+/// assert_eq!((brd.rows(), brd.cols(), brd.generation()), (8, 8, 4));
+/// assert_eq!(brd.population(), 5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoardBuilder {
+    size: Option<(usize, usize)>,
+    template: Option<Board>,
+    align: Alignment,
+    padding: Option<(isize, isize, isize, isize)>,
+    rule: Option<Rule>,
+    engine: Option<Arc<dyn Engine>>,
+    random: bool,
+    generations: usize,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        BoardBuilder::new()
+    }
+}
+
+impl BoardBuilder {
+    /// An empty 0×0 board, until given a size or template
+    pub fn new() -> Self {
+        BoardBuilder {
+            size: None,
+            template: None,
+            align: Alignment::Center,
+            padding: None,
+            rule: None,
+            engine: None,
+            random: false,
+            generations: 0,
+        }
+    }
+
+    /// The number of rows and columns (by default the template's size)
+    pub fn size(mut self, rows: usize, cols: usize) -> Self {
+        self.size = Some((rows, cols));
+        self
+    }
+
+    /// Start from a template, aligned within the board's size (which crops it if smaller)
+    pub fn template(mut self, template: Board) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Where the template goes within the board (centered by default)
+    pub fn align(mut self, align: Alignment) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Exact padding around the template instead of a size and alignment (negative padding
+    /// crops it)
+    pub fn padding(mut self, top: isize, right: isize, bottom: isize, left: isize) -> Self {
+        self.padding = Some((top, right, bottom, left));
+        self
+    }
+
+    /// The rule, replacing the template's (B3/S23 by default)
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// The engine computing generations, replacing the template's (by default
+    /// [`EngineKind::default`](crate::EngineKind::default))
+    pub fn engine(mut self, engine: Arc<dyn Engine>) -> Self {
+        self.engine = Some(engine);
+        self
+    }
+
+    /// Fill the board with random cells in any of the rule's states (whether the rule is given
+    /// before or after this), replacing any template
+    pub fn random(mut self) -> Self {
+        self.random = true;
+        self
+    }
+
+    /// How many generations to advance before returning the board
+    pub fn generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    /// The board, with everything given so far
+    pub fn build(self) -> Board {
+        let mut brd = match self.template {
+            Some(template) => {
+                let (top, right, bottom, left) = self.padding.unwrap_or_else(|| {
+                    let (rows, cols) = self.size.unwrap_or((template.rows(), template.cols()));
+
+                    alignment_padding(
+                        self.align,
+                        cols as isize - template.cols() as isize,
+                        rows as isize - template.rows() as isize,
+                    )
+                });
+                template.pad(top, right, bottom, left)
+            }
+            None => {
+                let (rows, cols) = self.size.unwrap_or_default();
+                Board::new(rows, cols)
+            }
+        };
+
+        if let Some(rule) = self.rule {
+            brd = brd.with_rule(rule);
+        }
+        // After the rule, so multi-state rules get all their states
+        if self.random {
+            brd = brd.random();
+        }
+        if let Some(engine) = self.engine {
+            brd = brd.with_engine(engine);
+        }
+        brd.generations()
+            .nth(self.generations)
+            .expect("generations never end")
+    }
+}
+
+/// The `(top, right, bottom, left)` padding placing a template as `align` says, given how
+/// much wider and taller the board is
+pub(crate) fn alignment_padding(
+    align: Alignment,
+    horizontal_padding: isize,
+    vertical_padding: isize,
+) -> (isize, isize, isize, isize) {
+    let (top, bottom) = match align {
+        Alignment::TopLeft | Alignment::Top | Alignment::TopRight => (0, vertical_padding),
+        Alignment::Left | Alignment::Center | Alignment::Right => (
+            vertical_padding / 2,
+            vertical_padding / 2 + vertical_padding % 2,
+        ),
+        Alignment::BottomLeft | Alignment::Bottom | Alignment::BottomRight => (vertical_padding, 0),
+    };
+    let (left, right) = match align {
+        Alignment::TopLeft | Alignment::Left | Alignment::BottomLeft => (0, horizontal_padding),
+        Alignment::Top | Alignment::Center | Alignment::Bottom => (
+            horizontal_padding / 2,
+            horizontal_padding / 2 + horizontal_padding % 2,
+        ),
+        Alignment::TopRight | Alignment::Right | Alignment::BottomRight => (horizontal_padding, 0),
+    };
+
+    (top, right, bottom, left)
+}
+
+#[test]
+fn test_alignment_padding() {
+    assert_eq!(alignment_padding(Alignment::Top, 2, 2), (0, 1, 2, 1));
+    assert_eq!(alignment_padding(Alignment::TopLeft, 2, 2), (0, 2, 2, 0));
+    assert_eq!(alignment_padding(Alignment::TopRight, 2, 2), (0, 0, 2, 2));
+    assert_eq!(alignment_padding(Alignment::Center, 2, 2), (1, 1, 1, 1));
+    assert_eq!(alignment_padding(Alignment::Left, 2, 2), (1, 2, 1, 0));
+    assert_eq!(alignment_padding(Alignment::Right, 2, 2), (1, 0, 1, 2));
+    assert_eq!(alignment_padding(Alignment::Bottom, 2, 2), (2, 1, 0, 1));
+    assert_eq!(alignment_padding(Alignment::BottomLeft, 2, 2), (2, 2, 0, 0));
+    assert_eq!(
+        alignment_padding(Alignment::BottomRight, 2, 2),
+        (2, 0, 0, 2)
+    );
+}
+
+#[test]
+fn test_builder() {
+    use crate::rule_table::{RuleTable, WIREWORLD};
+    use std::str::FromStr;
+
+    let blinker = Board::from_str("@@@").unwrap();
+
+    assert_eq!(BoardBuilder::new().build(), Board::new(0, 0));
+    assert_eq!(
+        Board::builder()
+            .size(3, 3)
+            .template(blinker.clone())
+            .build()
+            .to_string(),
+        "...\n@@@\n..."
+    );
+    assert_eq!(
+        Board::builder()
+            .size(2, 4)
+            .template(blinker.clone())
+            .align(Alignment::BottomRight)
+            .build()
+            .to_string(),
+        "....\n.@@@"
+    );
+    assert_eq!(
+        Board::builder()
+            .template(blinker.clone())
+            .padding(1, 0, 1, 0)
+            .generations(1)
+            .build()
+            .to_string(),
+        ".@.\n.@.\n.@."
+    );
+    // A board smaller than the template crops it
+    assert_eq!(
+        Board::builder()
+            .size(1, 1)
+            .template(blinker)
+            .build()
+            .to_string(),
+        "@"
+    );
+
+    let highlife = Rule::from_str("B36/S23").unwrap();
+    let random = Board::builder()
+        .size(20, 30)
+        .random()
+        .rule(highlife.clone())
+        .build();
+    assert_eq!((random.rows(), random.cols()), (20, 30));
+    assert_eq!(random.rule(), &highlife);
+
+    let wireworld = Rule::Table(RuleTable::parse(WIREWORLD).unwrap());
+    let soup = Board::builder()
+        .size(10, 10)
+        .random()
+        .rule(wireworld)
+        .build();
+    assert!(soup.states().iter().all(|&state| state < 4));
+    assert!(soup.states().iter().any(|&state| state > 1));
+}
@@ -0,0 +1,249 @@
+use clap::ValueEnum;
+
+use crate::board::Board;
+use crate::rule::Rule;
+use crate::stats::CellStats;
+
+/// Colors for states above 1 when the rule doesn't define its own
+const STATE_COLORS: [[u8; 3]; 6] = [
+    [255, 0, 0],
+    [255, 255, 0],
+    [0, 255, 0],
+    [0, 255, 255],
+    [0, 0, 255],
+    [255, 0, 255],
+];
+/// The Okabe-Ito palette, distinguishable with the common forms of color blindness
+const COLORBLIND_STATE_COLORS: [[u8; 3]; 6] = [
+    [230, 159, 0],
+    [86, 180, 233],
+    [0, 158, 115],
+    [240, 228, 66],
+    [0, 114, 178],
+    [204, 121, 167],
+];
+/// Live cells fade from the live color through these to violet as they age
+const AGE_GRADIENT: [[u8; 3]; 4] = [[255, 255, 0], [255, 128, 0], [255, 0, 0], [128, 0, 255]];
+/// Cells this old are drawn in the last color of `AGE_GRADIENT`
+const MAX_AGE: u32 = 100;
+/// Trails fade from this to the dead color
+const TRAIL_COLOR: [u8; 3] = [0, 128, 255];
+/// Generations a trail takes to fade out
+const TRAIL_LENGTH: u32 = 16;
+/// Heat rises from the dead color through these
+const HEAT_GRADIENT: [[u8; 3]; 4] = [[0, 0, 192], [192, 0, 0], [255, 192, 0], [255, 255, 192]];
+
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color by cell state
+    #[default]
+    State,
+    /// Color live cells by how many generations they've been alive
+    Age,
+    /// Leave fading trails behind cells that died recently
+    Trails,
+    /// Color every cell by how often it has changed
+    Heatmap,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    Colorblind,
+}
+
+/// The colors everything is drawn with, from a theme and any overrides
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Colors {
+    pub(crate) live: [u8; 3],
+    pub(crate) dead: [u8; 3],
+    /// Around the board in the window
+    pub(crate) background: [u8; 3],
+    pub(crate) grid: [u8; 3],
+    /// For states above 1 when the rule doesn't define its own
+    pub(crate) states: [[u8; 3]; 6],
+}
+
+impl Theme {
+    pub(crate) fn colors(self) -> Colors {
+        match self {
+            Theme::Dark => Colors {
+                live: [255, 255, 255],
+                dead: [0, 0, 0],
+                background: [38, 38, 38],
+                grid: [64, 64, 64],
+                states: STATE_COLORS,
+            },
+            Theme::Light => Colors {
+                live: [0, 0, 0],
+                dead: [255, 255, 255],
+                background: [200, 200, 200],
+                grid: [192, 192, 192],
+                states: STATE_COLORS,
+            },
+            Theme::HighContrast => Colors {
+                live: [255, 255, 0],
+                dead: [0, 0, 0],
+                background: [0, 0, 96],
+                grid: [255, 255, 255],
+                states: STATE_COLORS,
+            },
+            Theme::Colorblind => Colors {
+                live: [255, 255, 255],
+                dead: [0, 0, 0],
+                background: [38, 38, 38],
+                grid: [64, 64, 64],
+                states: COLORBLIND_STATE_COLORS,
+            },
+        }
+    }
+}
+
+/// Parse a hex color such as `#ff8000`, `ff8000` or `#f80`
+pub(crate) fn parse_hex_color(s: &str) -> Result<[u8; 3], String> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()
+        .ok_or_else(|| format!("'{s}' is not a hex color"))?;
+
+    match digits[..] {
+        [r, g, b] => Ok([r * 17, g * 17, b * 17]),
+        [r1, r2, g1, g2, b1, b2] => Ok([r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2]),
+        _ => Err(format!(
+            "'{s}' is not a hex color (expected #rgb or #rrggbb)"
+        )),
+    }
+}
+
+fn opaque([r, g, b]: [u8; 3]) -> [u8; 4] {
+    [r, g, b, 255]
+}
+
+pub(crate) fn cell_color(colors: &Colors, rule: &Rule, state: u8) -> [u8; 4] {
+    match (rule.color(state), state) {
+        (Some(rgb), _) => opaque(rgb),
+        (None, 0) => opaque(colors.dead),
+        (None, 1) => opaque(colors.live),
+        (None, n) => opaque(colors.states[(n as usize - 2) % colors.states.len()]),
+    }
+}
+
+/// The color a fraction `t` (from 0 to 1) of the way along `gradient`
+fn gradient_color(gradient: &[[u8; 3]], t: f64) -> [u8; 4] {
+    let position = t.clamp(0.0, 1.0) * (gradient.len() - 1) as f64;
+    let (i, frac) = (position.floor() as usize, position.fract());
+    let (from, to) = (gradient[i], gradient[(i + 1).min(gradient.len() - 1)]);
+    let mix = |c: usize| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * frac).round() as u8;
+
+    [mix(0), mix(1), mix(2), 255]
+}
+
+/// RGBA pixels for every cell of `brd`, in row-major order
+pub(crate) fn pixels(brd: &Board, stats: &CellStats, mode: ColorMode, colors: &Colors) -> Vec<u8> {
+    let rule = brd.rule();
+    let max_activity = stats.max_activity().max(1) as f64;
+    let age: Vec<[u8; 3]> = [colors.live].into_iter().chain(AGE_GRADIENT).collect();
+    let trail = [TRAIL_COLOR, colors.dead];
+    let heat: Vec<[u8; 3]> = [colors.dead].into_iter().chain(HEAT_GRADIENT).collect();
+
+    brd.states()
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &state)| match (mode, state) {
+            (ColorMode::State, _) => cell_color(colors, rule, state),
+            (ColorMode::Age, 0) | (ColorMode::Trails, 1..) => cell_color(colors, rule, state),
+            (ColorMode::Age, _) => gradient_color(&age, (stats.age(i) - 1) as f64 / MAX_AGE as f64),
+            (ColorMode::Trails, 0) => match stats.since_death(i) {
+                n if n < TRAIL_LENGTH => gradient_color(&trail, n as f64 / TRAIL_LENGTH as f64),
+                _ => opaque(colors.dead),
+            },
+            (ColorMode::Heatmap, 0) => {
+                gradient_color(&heat, stats.activity(i) as f64 / max_activity)
+            }
+            (ColorMode::Heatmap, _) => opaque(colors.live),
+        })
+        .collect()
+}
+
+#[test]
+fn test_cell_color() {
+    let (life, dark) = (Rule::default(), Theme::Dark.colors());
+
+    assert_eq!(cell_color(&dark, &life, 0), [0, 0, 0, 255]);
+    assert_eq!(cell_color(&dark, &life, 1), [255, 255, 255, 255]);
+    assert_eq!(cell_color(&dark, &life, 2), [255, 0, 0, 255]);
+    assert_eq!(cell_color(&dark, &life, 8), [255, 0, 0, 255]);
+
+    let light = Theme::Light.colors();
+    assert_eq!(cell_color(&light, &life, 1), [0, 0, 0, 255]);
+    assert_eq!(
+        cell_color(&Theme::Colorblind.colors(), &life, 2),
+        [230, 159, 0, 255]
+    );
+}
+
+#[test]
+fn test_parse_hex_color() {
+    assert_eq!(parse_hex_color("#ff8000"), Ok([255, 128, 0]));
+    assert_eq!(parse_hex_color("1A2b3C"), Ok([26, 43, 60]));
+    assert_eq!(parse_hex_color("#f80"), Ok([255, 136, 0]));
+    assert_eq!(
+        parse_hex_color("#ff80"),
+        Err("'#ff80' is not a hex color (expected #rgb or #rrggbb)".to_string())
+    );
+    assert_eq!(
+        parse_hex_color("puppies"),
+        Err("'puppies' is not a hex color".to_string())
+    );
+}
+
+#[test]
+fn test_gradient_color() {
+    let gradient = [[0, 0, 0], [200, 100, 0]];
+
+    assert_eq!(gradient_color(&gradient, 0.0), [0, 0, 0, 255]);
+    assert_eq!(gradient_color(&gradient, 0.5), [100, 50, 0, 255]);
+    assert_eq!(gradient_color(&gradient, 1.0), [200, 100, 0, 255]);
+    assert_eq!(gradient_color(&gradient, 7.0), [200, 100, 0, 255]);
+}
+
+#[test]
+fn test_pixels() {
+    use std::str::FromStr;
+
+    let brd = Board::from_str("...\n@@@\n...").unwrap();
+    let mut stats = CellStats::new(&brd);
+    let next = brd.next_generation();
+    stats.update(&next);
+    let colors = Theme::Dark.colors();
+    let pixel = |mode, i: usize| pixels(&next, &stats, mode, &colors)[i * 4..i * 4 + 4].to_vec();
+    let (live, dead) = ([255, 255, 255, 255], [0, 0, 0, 255]);
+
+    assert_eq!(pixel(ColorMode::State, 4), live);
+    // The centre has survived a generation, the cell above it was just born
+    assert_eq!(pixel(ColorMode::Age, 1), live);
+    assert_ne!(pixel(ColorMode::Age, 4), live);
+    assert_eq!(pixel(ColorMode::Trails, 3), [0, 128, 255, 255]);
+    assert_eq!(pixel(ColorMode::Trails, 0), dead);
+    assert_eq!(pixel(ColorMode::Heatmap, 3), [255, 255, 192, 255]);
+    assert_eq!(pixel(ColorMode::Heatmap, 0), dead);
+
+    // Newborn cells start from the theme's live color
+    let light = Theme::Light.colors();
+    let ages = pixels(&next, &stats, ColorMode::Age, &light);
+    assert_eq!(ages[4..8], [0, 0, 0, 255]);
+    assert_ne!(ages[16..20], [0, 0, 0, 255]);
+    let custom = Colors {
+        live: [0, 200, 0],
+        ..light
+    };
+    assert_eq!(
+        pixels(&next, &stats, ColorMode::Age, &custom)[4..8],
+        [0, 200, 0, 255]
+    );
+}
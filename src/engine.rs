@@ -0,0 +1,657 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use clap::ValueEnum;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::bitwise::{Kernel, Scratch};
+use crate::board::Board;
+use crate::rule::{PrefixSums, Rule};
+
+/// A way of computing generations. Every engine gives the same results; they differ in
+/// speed depending on the size and contents of the board.
+pub trait Engine: fmt::Debug + Send + Sync {
+    /// Write the state of every cell of `brd` in the next generation into `next`, row by row.
+    /// `next` holds [`Board::len`] cells, with whatever states were left in it before.
+    fn step(&self, brd: &Board, next: &mut [u8]);
+}
+
+/// The built-in engines, as chosen with `--engine`
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EngineKind {
+    /// One cell at a time on a single thread
+    #[cfg_attr(not(feature = "rayon"), default)]
+    Serial,
+    /// Cells shared out across a thread pool
+    #[cfg(feature = "rayon")]
+    #[default]
+    Rayon,
+    /// Rows packed into 64-bit words, counting neighbors for a whole word at once
+    Bitwise,
+    /// Memoized quadtree, fast on boards with repeating structure
+    Hashlife,
+    /// Only visits live cells and their neighbors, fast on mostly empty boards
+    Sparse,
+    /// Skips tiles that have settled, fast once most of the board is still
+    Tiled,
+}
+
+impl EngineKind {
+    /// A new engine of this kind
+    pub fn engine(self) -> Arc<dyn Engine> {
+        match self {
+            EngineKind::Serial => Arc::new(Serial),
+            #[cfg(feature = "rayon")]
+            EngineKind::Rayon => Arc::new(Rayon),
+            EngineKind::Bitwise => Arc::new(Bitwise::default()),
+            EngineKind::Hashlife => Arc::new(HashLife::default()),
+            EngineKind::Sparse => Arc::new(Sparse),
+            EngineKind::Tiled => Arc::new(Tiled::default()),
+        }
+    }
+}
+
+/// Bit masks of the neighbor counts at which cells are born and survive, for the life-like
+/// rules the specialized engines handle (others fall back to [`Serial`])
+pub(crate) fn life_like(rule: &Rule) -> Option<(u16, u16)> {
+    let mask = |counts: &[usize]| counts.iter().fold(0, |mask, &n| mask | 1 << n);
+
+    match rule {
+        Rule::LifeLike { born, survive } => Some((mask(born), mask(survive))),
+        Rule::LargerThanLife(_) | Rule::Table(_) => None,
+    }
+}
+
+/// Write the next states of `row`, from column `first_col` on, into `out`. Life-like rules
+/// count neighbors with a window of three column sums (each covering the rows above, at and
+/// below `row`) sliding along the row, so each cell is read three times rather than nine;
+/// other rules go cell by cell.
+fn step_row(
+    brd: &Board,
+    sums: Option<&PrefixSums>,
+    rule: Option<(u16, u16)>,
+    (row, first_col): (usize, usize),
+    out: &mut [u8],
+) {
+    let cols = brd.cols();
+    let Some((born, survive)) = rule else {
+        for (col, state) in (first_col..).zip(out) {
+            *state = brd.successor_cell(sums, row * cols + col);
+        }
+        return;
+    };
+
+    let states = brd.states();
+    let line = |row: usize| states.get(row * cols..(row + 1) * cols);
+    let (above, current, below) = (row.checked_sub(1).and_then(line), line(row), line(row + 1));
+    let live = |line: Option<&[u8]>, col: usize| line.map_or(0, |line| (line[col] != 0) as u8);
+    let column = |col: usize| {
+        if col < cols {
+            live(above, col) + live(current, col) + live(below, col)
+        } else {
+            0
+        }
+    };
+
+    let (mut left, mut middle) = (
+        first_col.checked_sub(1).map_or(0, column),
+        column(first_col),
+    );
+    for (col, state) in (first_col..).zip(out) {
+        let right = column(col + 1);
+        let alive = live(current, col);
+        let rule = if alive == 1 { survive } else { born };
+
+        *state = (rule >> (left + middle + right - alive) & 1) as u8;
+        (left, middle) = (middle, right);
+    }
+}
+
+/// Computes the board row by row on the current thread; handles every rule
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Serial;
+
+impl Engine for Serial {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        let (sums, rule) = (brd.prefix_sums(), life_like(brd.rule()));
+
+        for (row, out) in next.chunks_mut(brd.cols().max(1)).enumerate() {
+            step_row(brd, sums.as_ref(), rule, (row, 0), out);
+        }
+    }
+}
+
+/// Computes rows in parallel with rayon, as [`Serial`] does; handles every rule
+#[cfg(feature = "rayon")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Rayon;
+
+#[cfg(feature = "rayon")]
+impl Engine for Rayon {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        let (sums, rule) = (brd.prefix_sums(), life_like(brd.rule()));
+
+        next.par_chunks_mut(brd.cols().max(1))
+            .enumerate()
+            .for_each(|(row, out)| step_row(brd, sums.as_ref(), rule, (row, 0), out));
+    }
+}
+
+/// Packs each row into 64-bit words and adds up the eight neighbor bit planes with a bitwise
+/// adder, finding 64 cells' next states with a few dozen word operations. With the `simd`
+/// feature, x86-64 CPUs with AVX2 or SSE2 work on four or two words at once.
+#[derive(Default)]
+pub struct Bitwise {
+    scratch: Mutex<Scratch>,
+}
+
+impl fmt::Debug for Bitwise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Bitwise").finish_non_exhaustive()
+    }
+}
+
+impl Engine for Bitwise {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        match life_like(brd.rule()) {
+            Some(rule) => {
+                let mut scratch = self.scratch.lock().unwrap_or_else(PoisonError::into_inner);
+                Kernel::detect().step(&mut scratch, brd, rule, next)
+            }
+            None => Serial.step(brd, next),
+        }
+    }
+}
+
+/// Counts neighbors outward from each live cell, so dead space costs nothing
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sparse;
+
+impl Engine for Sparse {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        let Some((born, survive)) = life_like(brd.rule()) else {
+            return Serial.step(brd, next);
+        };
+        // Births from nothing would make every dead cell matter
+        if born & 1 == 1 {
+            return Serial.step(brd, next);
+        }
+        let (rows, cols) = (brd.rows(), brd.cols());
+        let states = brd.states();
+        let live: Vec<usize> = (0..states.len()).filter(|&i| states[i] != 0).collect();
+
+        let mut counts: HashMap<usize, u32> = HashMap::with_capacity(live.len() * 8);
+        for &i in &live {
+            let (row, col) = (i / cols, i % cols);
+            for r in row.saturating_sub(1)..=(row + 1).min(rows - 1) {
+                for c in col.saturating_sub(1)..=(col + 1).min(cols - 1) {
+                    if (r, c) != (row, col) {
+                        *counts.entry(r * cols + c).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        next.fill(0);
+        for (&i, &count) in &counts {
+            let rule = if states[i] != 0 { survive } else { born };
+            next[i] = (rule >> count & 1) as u8;
+        }
+        // Live cells without a single neighbor
+        if survive & 1 == 1 {
+            for &i in live.iter().filter(|i| !counts.contains_key(i)) {
+                next[i] = 1;
+            }
+        }
+    }
+}
+
+/// The width and height in cells of the tiles [`Tiled`] tracks
+const TILE: usize = 32;
+
+/// Splits the board into tiles and only recomputes those with a change last generation
+/// within reach of them; the rest are copied unchanged. Handles every rule.
+///
+/// Which tiles changed is only known for the board this engine returned last, so stepping
+/// any other board (e.g. after editing it) recomputes every tile once.
+#[derive(Default)]
+pub struct Tiled {
+    memo: Mutex<TileMemo>,
+}
+
+impl fmt::Debug for Tiled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tiled").finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct TileMemo {
+    rule: Option<Rule>,
+    cols: usize,
+    /// The cells of the last generation computed
+    states: Vec<u8>,
+    /// Whether each tile changed in that generation, by row
+    changed: Vec<bool>,
+    /// Whether each tile changes in the generation being computed, kept to be reused
+    changing: Vec<bool>,
+}
+
+impl Engine for Tiled {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        let (rows, cols) = (brd.rows(), brd.cols());
+        let (tile_rows, tile_cols) = (rows.div_ceil(TILE), cols.div_ceil(TILE));
+        // How many tiles away a change can affect a cell in a generation
+        let reach = brd.rule().range().div_ceil(TILE);
+        let mut memo = self.memo.lock().unwrap_or_else(PoisonError::into_inner);
+        let memo = &mut *memo;
+
+        if memo.rule.as_ref() != Some(brd.rule()) {
+            memo.rule = Some(brd.rule().clone());
+            memo.states.clear();
+        }
+        if memo.cols != cols || memo.states != brd.states() {
+            memo.cols = cols;
+            memo.changed.clear();
+            memo.changed.resize(tile_rows * tile_cols, true);
+        }
+        memo.changing.clear();
+        memo.changing.resize(tile_rows * tile_cols, false);
+
+        let (sums, rule) = (brd.prefix_sums(), life_like(brd.rule()));
+        let states = brd.states();
+        for (tile_row, tile_col) in (0..tile_rows).flat_map(|r| (0..tile_cols).map(move |c| (r, c)))
+        {
+            let active = (tile_row.saturating_sub(reach)..=(tile_row + reach).min(tile_rows - 1))
+                .flat_map(|r| {
+                    (tile_col.saturating_sub(reach)..=(tile_col + reach).min(tile_cols - 1))
+                        .map(move |c| (r, c))
+                })
+                .any(|(r, c)| memo.changed[r * tile_cols + c]);
+            let col_range = tile_col * TILE..((tile_col + 1) * TILE).min(cols);
+
+            for row in tile_row * TILE..((tile_row + 1) * TILE).min(rows) {
+                let cells = row * cols + col_range.start..row * cols + col_range.end;
+                if !active {
+                    next[cells.clone()].copy_from_slice(&states[cells]);
+                    continue;
+                }
+                step_row(
+                    brd,
+                    sums.as_ref(),
+                    rule,
+                    (row, col_range.start),
+                    &mut next[cells.clone()],
+                );
+                memo.changing[tile_row * tile_cols + tile_col] |=
+                    next[cells.clone()] != states[cells];
+            }
+        }
+
+        memo.states.clear();
+        memo.states.extend_from_slice(next);
+        mem::swap(&mut memo.changed, &mut memo.changing);
+    }
+}
+
+/// Stores the board as a quadtree of shared, deduplicated nodes and memoizes the next
+/// generation of each node's center, so repeated structure (and empty space) is only
+/// computed once. The memo is kept between generations.
+#[derive(Default)]
+pub struct HashLife {
+    tree: Mutex<Quadtree>,
+}
+
+impl fmt::Debug for HashLife {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nodes = self.tree.lock().map_or(0, |tree| tree.children.len());
+        f.debug_struct("HashLife").field("nodes", &nodes).finish()
+    }
+}
+
+impl Engine for HashLife {
+    fn step(&self, brd: &Board, next: &mut [u8]) {
+        let Some(rule) = life_like(brd.rule()) else {
+            return Serial.step(brd, next);
+        };
+        if brd.len() == 0 {
+            return;
+        }
+        let mut tree = self.tree.lock().unwrap_or_else(|err| err.into_inner());
+        if tree.rule != Some(rule) || tree.children.len() > MAX_NODES {
+            *tree = Quadtree {
+                rule: Some(rule),
+                ..Quadtree::default()
+            };
+        }
+
+        // The board's top left sits a quarter of the way into the root, so the next
+        // generation of its center half covers the board
+        let dims = brd.rows().max(brd.cols());
+        let level = (usize::BITS - (dims - 1).leading_zeros() + 1).max(3);
+        let offset = 1 << (level - 2);
+        let root = tree.build(brd, level, -offset, -offset);
+        let center = tree.step(root, level);
+
+        next.fill(0);
+        tree.write(center, level - 1, 0, 0, brd.cols(), next);
+    }
+}
+
+/// Nodes beyond which the memo is thrown away, to bound memory on chaotic boards
+const MAX_NODES: usize = 1 << 22;
+
+/// Hash-consed quadtree nodes. Ids 0 and 1 are a dead and a live cell; every other id is a
+/// node of 2^level × 2^level cells made of four children one level down.
+#[derive(Default)]
+struct Quadtree {
+    /// The born and survive masks the memo was computed for
+    rule: Option<(u16, u16)>,
+    /// The `[nw, ne, sw, se]` children of each node, by id less 2
+    children: Vec<[u32; 4]>,
+    ids: HashMap<[u32; 4], u32>,
+    /// The node one level down holding the center of each node a generation later
+    steps: HashMap<u32, u32>,
+    /// The all-dead node at each level
+    empty: Vec<u32>,
+}
+
+impl Quadtree {
+    fn node(&mut self, children: [u32; 4]) -> u32 {
+        if let Some(&id) = self.ids.get(&children) {
+            return id;
+        }
+        let id = self.children.len() as u32 + 2;
+        self.children.push(children);
+        self.ids.insert(children, id);
+        id
+    }
+
+    fn children(&self, id: u32) -> [u32; 4] {
+        self.children[id as usize - 2]
+    }
+
+    fn empty(&mut self, level: u32) -> u32 {
+        if self.empty.is_empty() {
+            self.empty.push(0);
+        }
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().expect("level 0 exists");
+            let id = self.node([below; 4]);
+            self.empty.push(id);
+        }
+        self.empty[level as usize]
+    }
+
+    /// The node of `level` whose top-left cell is at `row`, `col` on the board
+    fn build(&mut self, brd: &Board, level: u32, row: isize, col: isize) -> u32 {
+        let size = 1 << level;
+        if row >= brd.rows() as isize
+            || col >= brd.cols() as isize
+            || row + size <= 0
+            || col + size <= 0
+        {
+            return self.empty(level);
+        }
+        if level == 0 {
+            return (brd.cell(row as usize, col as usize) != 0) as u32;
+        }
+
+        let half = size / 2;
+        let children = [(0, 0), (0, half), (half, 0), (half, half)]
+            .map(|(r, c)| self.build(brd, level - 1, row + r, col + c));
+        self.node(children)
+    }
+
+    /// Write the cells of the node of `level` with its top-left cell at `row`, `col`
+    fn write(&self, id: u32, level: u32, row: usize, col: usize, cols: usize, out: &mut [u8]) {
+        let rows = out.len() / cols;
+        if row >= rows || col >= cols || self.empty.get(level as usize) == Some(&id) {
+            return;
+        }
+        if level == 0 {
+            out[row * cols + col] = id as u8;
+            return;
+        }
+
+        let half = 1 << (level - 1);
+        let [nw, ne, sw, se] = self.children(id);
+        self.write(nw, level - 1, row, col, cols, out);
+        self.write(ne, level - 1, row, col + half, cols, out);
+        self.write(sw, level - 1, row + half, col, cols, out);
+        self.write(se, level - 1, row + half, col + half, cols, out);
+    }
+
+    /// The middle half of a node, a level down
+    fn center(&mut self, id: u32) -> u32 {
+        let [nw, ne, sw, se] = self.children(id);
+        let children = [
+            self.children(nw)[3],
+            self.children(ne)[2],
+            self.children(sw)[1],
+            self.children(se)[0],
+        ];
+        self.node(children)
+    }
+
+    /// The node straddling the boundary between side-by-side nodes `west` and `east`
+    fn between_horizontal(&mut self, west: u32, east: u32) -> u32 {
+        let ([_, w_ne, _, w_se], [e_nw, _, e_sw, _]) = (self.children(west), self.children(east));
+        self.node([w_ne, e_nw, w_se, e_sw])
+    }
+
+    /// The node straddling the boundary between `north` and `south` above it
+    fn between_vertical(&mut self, north: u32, south: u32) -> u32 {
+        let ([_, _, n_sw, n_se], [s_nw, s_ne, _, _]) = (self.children(north), self.children(south));
+        self.node([n_sw, n_se, s_nw, s_ne])
+    }
+
+    /// The center of a node of `level` (at least 2) one generation later
+    fn step(&mut self, id: u32, level: u32) -> u32 {
+        if let Some(&next) = self.steps.get(&id) {
+            return next;
+        }
+
+        let next = if level == 2 {
+            self.step_leaf(id)
+        } else {
+            let [nw, ne, sw, se] = self.children(id);
+            // The nine overlapping nodes a level down, in rows
+            let n = [
+                nw,
+                self.between_horizontal(nw, ne),
+                ne,
+                self.between_vertical(nw, sw),
+                self.center(id),
+                self.between_vertical(ne, se),
+                sw,
+                self.between_horizontal(sw, se),
+                se,
+            ];
+            let c = n.map(|id| self.center(id));
+            let quadrants = [
+                [c[0], c[1], c[3], c[4]],
+                [c[1], c[2], c[4], c[5]],
+                [c[3], c[4], c[6], c[7]],
+                [c[4], c[5], c[7], c[8]],
+            ]
+            .map(|children| {
+                let quadrant = self.node(children);
+                self.step(quadrant, level - 1)
+            });
+            self.node(quadrants)
+        };
+
+        self.steps.insert(id, next);
+        next
+    }
+
+    /// The center 2×2 cells of a 4×4 node one generation later
+    fn step_leaf(&mut self, id: u32) -> u32 {
+        let (born, survive) = self.rule.expect("the rule is set before stepping");
+        let mut cells = [[0u8; 4]; 4];
+        for (q, child) in self.children(id).into_iter().enumerate() {
+            for (i, cell) in self.children(child).into_iter().enumerate() {
+                cells[q / 2 * 2 + i / 2][q % 2 * 2 + i % 2] = cell as u8;
+            }
+        }
+
+        let next = [(1, 1), (1, 2), (2, 1), (2, 2)].map(|(row, col): (usize, usize)| {
+            let neighbors: u8 = (row - 1..=row + 1)
+                .flat_map(|r| (col - 1..=col + 1).map(move |c| (r, c)))
+                .filter(|&pos| pos != (row, col))
+                .map(|(r, c)| cells[r][c])
+                .sum();
+            let rule = if cells[row][col] == 1 { survive } else { born };
+            (rule >> neighbors & 1) as u32
+        });
+        self.node(next)
+    }
+}
+
+/// Run `brd` with every engine alongside [`Serial`], checking they agree each generation
+#[cfg(test)]
+fn assert_conforms(brd: &Board, generations: usize) {
+    for &kind in EngineKind::value_variants() {
+        let mut expected = brd.with_engine(Arc::new(Serial));
+        let mut actual = brd.with_engine(kind.engine());
+
+        for _ in 0..generations {
+            expected = expected.next_generation();
+            actual = actual.next_generation();
+            assert_eq!(
+                actual,
+                expected,
+                "{kind:?} on a {}x{} {} board at generation {}",
+                brd.rows(),
+                brd.cols(),
+                brd.rule(),
+                expected.generation()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_engines_conform() {
+    use std::str::FromStr;
+
+    let sizes = [
+        (0, 0),
+        (1, 1),
+        (1, 70),
+        (70, 1),
+        (5, 63),
+        (9, 64),
+        (7, 65),
+        (40, 130),
+    ];
+    let rules = ["B3/S23", "B36/S23", "B2/S", "B1/S012345678", "B0/S8"];
+
+    for rule in rules {
+        let rule = Rule::from_str(rule).unwrap();
+        for (rows, cols) in sizes {
+            let brd = Board::new_with_rule(rows, cols, rule.clone()).random();
+            assert_conforms(&brd, 30);
+        }
+    }
+}
+
+#[test]
+fn test_engines_conform_patterns() {
+    use crate::patterns::PATTERNS;
+
+    for pattern in PATTERNS {
+        let brd = crate::parse_template(pattern.name).unwrap();
+        assert_conforms(&brd.pad(10, 10, 10, 10), 40);
+    }
+}
+
+#[test]
+fn test_engines_fall_back() {
+    use crate::rule_table::{RuleTable, WIREWORLD};
+    use std::str::FromStr;
+
+    let bosco = Rule::from_str("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    assert_conforms(&Board::new_with_rule(30, 30, bosco).random(), 5);
+
+    let wireworld = Rule::Table(RuleTable::parse(WIREWORLD).unwrap());
+    assert_conforms(&Board::new_with_rule(20, 20, wireworld).random(), 10);
+}
+
+#[test]
+fn test_hashlife_memo() {
+    use std::str::FromStr;
+
+    // A memo from one rule isn't reused for another
+    let engine = EngineKind::Hashlife.engine();
+    let blinker = Board::from_str("...\n@@@\n...")
+        .unwrap()
+        .with_engine(engine);
+    let highlife = blinker.with_rule(Rule::from_str("B2/S").unwrap());
+
+    assert_eq!(blinker.next_generation().to_string(), ".@.\n.@.\n.@.");
+    assert_eq!(highlife.next_generation().to_string(), "@.@\n...\n@.@");
+    assert_eq!(blinker.next_generation().to_string(), ".@.\n.@.\n.@.");
+}
+
+#[test]
+fn test_tiled() {
+    use std::str::FromStr;
+
+    // A glider crossing tile boundaries, next to a blinker that keeps its tile busy
+    let mut brd = Board::new(70, 100);
+    brd.paste(&Board::from_str(".@.\n..@\n@@@").unwrap(), 25, 25);
+    brd.paste(&Board::from_str("@@@").unwrap(), 60, 90);
+    assert_conforms(&brd, 120);
+
+    let engine = EngineKind::Tiled.engine();
+    let (mut tiled, mut serial) = (brd.with_engine(engine.clone()), brd.clone());
+    let mut other = Board::new(70, 100).random().with_engine(engine);
+    for generation in 0..60 {
+        tiled.step_in_place();
+        serial = serial.serial_next_generation();
+        // Edits, and other boards sharing the engine, are noticed
+        if generation % 20 == 0 {
+            tiled.set(5, 5, 1);
+            serial.set(5, 5, 1);
+            other.step_in_place();
+        }
+        assert_eq!(tiled, serial);
+    }
+
+    let bosco = Rule::from_str("R40,C0,M1,S34..58,B34..45,NM").unwrap();
+    assert_conforms(&Board::new_with_rule(100, 100, bosco).random(), 3);
+}
+
+#[test]
+fn test_row_window() {
+    use std::str::FromStr;
+
+    for rule in ["B3/S23", "B36/S23", "B0/S8", "B2/S"] {
+        let rule = Rule::from_str(rule).unwrap();
+        for (rows, cols) in [(0, 0), (1, 1), (1, 9), (9, 1), (2, 2), (17, 23)] {
+            let brd = Board::new_with_rule(rows, cols, rule.clone()).random();
+            let cell_by_cell: Vec<u8> = (0..brd.len())
+                .map(|cell| brd.successor_cell(None, cell))
+                .collect();
+            let mut next = vec![0; brd.len()];
+
+            Serial.step(&brd, &mut next);
+            assert_eq!(next, cell_by_cell, "{rule} on {rows}x{cols}");
+
+            // Rows can also be computed in parts, as for tiles
+            next.fill(0);
+            let rule = life_like(brd.rule());
+            for (row, out) in next.chunks_mut(cols.max(1)).enumerate() {
+                let (first, second) = out.split_at_mut(cols / 2);
+                step_row(&brd, None, rule, (row, 0), first);
+                step_row(&brd, None, rule, (row, cols / 2), second);
+            }
+            assert_eq!(next, cell_by_cell);
+        }
+    }
+}
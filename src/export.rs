@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use image::{imageops, ImageBuffer, ImageResult, Rgba};
+
+use crate::board::Board;
+#[cfg(test)]
+use crate::color::Theme;
+use crate::color::{pixels, ColorMode, Colors};
+use crate::stats::CellStats;
+
+/// Save `brd` as an image with `scale` pixels per cell side; the format is picked from the
+/// file extension
+pub(crate) fn save_image(
+    brd: &Board,
+    stats: &CellStats,
+    mode: ColorMode,
+    colors: &Colors,
+    scale: u32,
+    path: &Path,
+) -> ImageResult<()> {
+    let (cols, rows) = (brd.cols() as u32, brd.rows() as u32);
+    let img: ImageBuffer<Rgba<u8>, _> =
+        ImageBuffer::from_raw(cols, rows, pixels(brd, stats, mode, colors))
+            .expect("one pixel per cell");
+
+    imageops::resize(
+        &img,
+        cols * scale,
+        rows * scale,
+        imageops::FilterType::Nearest,
+    )
+    .save(path)
+}
+
+#[test]
+fn test_save_image() {
+    use std::str::FromStr;
+
+    let brd = Board::from_str(".@\n@.").unwrap();
+    let path = std::env::temp_dir().join(format!("rust-life-{}.png", std::process::id()));
+
+    let colors = Theme::Light.colors();
+
+    save_image(
+        &brd,
+        &CellStats::new(&brd),
+        ColorMode::State,
+        &colors,
+        3,
+        &path,
+    )
+    .unwrap();
+    let img = image::open(&path).unwrap().to_rgba8();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(img.dimensions(), (6, 6));
+    assert_eq!(img.get_pixel(0, 0), &Rgba([255, 255, 255, 255]));
+    assert_eq!(img.get_pixel(5, 0), &Rgba([0, 0, 0, 255]));
+    assert_eq!(img.get_pixel(2, 3), &Rgba([0, 0, 0, 255]));
+}
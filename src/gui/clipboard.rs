@@ -0,0 +1,33 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Commands that put their standard input on the system clipboard, tried in order
+const COPY_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("clip", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Put `text` on the system clipboard, returning whether any clipboard command accepted it.
+/// This is best effort: the internal clipboard works without one.
+pub(crate) fn copy(text: &str) -> bool {
+    COPY_COMMANDS.iter().any(|&(program, args)| {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, fs};
+
+use crate::board::Board;
+use crate::rle::{parse_rle_with, to_rle};
+use crate::rule::Rule;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct FileErr(String);
+
+impl Error for FileErr {}
+
+impl fmt::Display for FileErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+pub(crate) fn is_image(path: &Path) -> bool {
+    has_extension(path, "png")
+}
+
+/// A file in the current directory named after the current time, e.g. `life-1700000000.rle`
+pub(crate) fn timestamped(extension: &str) -> PathBuf {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+
+    PathBuf::from(format!("life-{now}.{extension}"))
+}
+
+/// Save a board as `@`/`.` text if the path ends in `.txt`, otherwise as RLE (which also
+/// keeps the rule and generation)
+pub(crate) fn save(brd: &Board, path: &Path) -> Result<(), FileErr> {
+    let contents = if has_extension(path, "txt") {
+        format!("{brd}\n")
+    } else {
+        to_rle(brd)
+    };
+
+    fs::write(path, contents).map_err(|err| FileErr(format!("{}: {err}", path.display())))
+}
+
+/// Load a board saved by `save`, keeping `current` if the RLE names it (as with rule tables)
+pub(crate) fn load(path: &Path, current: &Rule) -> Result<Board, FileErr> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| FileErr(format!("{}: {err}", path.display())))?;
+    let brd = if has_extension(path, "txt") {
+        Board::from_str(contents.trim_end())
+    } else {
+        parse_rle_with(&contents, current)
+    };
+
+    brd.map_err(|err| FileErr(format!("{}: {err}", path.display())))
+}
+
+#[test]
+fn test_save_load() {
+    use crate::rule_table::{RuleTable, WIREWORLD};
+
+    let dir = std::env::temp_dir();
+    let brd = Board::from_str(".@.\n..@\n@@@")
+        .unwrap()
+        .with_rule(Rule::from_str("B36/S23").unwrap())
+        .next_generation();
+
+    for name in ["rust-life-save.rle", "rust-life-save.txt"] {
+        let path = dir.join(format!("{}-{name}", std::process::id()));
+        save(&brd, &path).unwrap();
+        let loaded = load(&path, &Rule::default());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap().to_string(), brd.to_string());
+    }
+
+    // Only RLE keeps the rule and generation
+    let ltl = Board::from_str("@@@\n@.@\n@@@")
+        .unwrap()
+        .with_rule(Rule::from_str("R2,C0,M1,S3..8,B3..5,NN").unwrap())
+        .next_generation()
+        .next_generation();
+    let wireworld = Rule::Table(RuleTable::parse(WIREWORLD).unwrap());
+    let table = Board::from_str("CBACC")
+        .unwrap()
+        .with_rule(wireworld.clone())
+        .next_generation();
+    let path = dir.join(format!("{}-rust-life-resume.rle", std::process::id()));
+
+    for (brd, current) in [
+        (&brd, Rule::default()),
+        (&ltl, Rule::default()),
+        (&table, wireworld),
+    ] {
+        save(brd, &path).unwrap();
+        let loaded = load(&path, &current).unwrap();
+
+        assert_eq!(loaded.rule(), brd.rule());
+        assert_eq!(loaded.generation(), brd.generation());
+        assert_eq!(&loaded, brd);
+    }
+    // A rule table other than the current one can't be rebuilt from its name
+    assert!(load(&path, &Rule::default()).is_err());
+    fs::remove_file(&path).unwrap();
+
+    assert!(load(&dir.join("rust-life-does-not-exist.rle"), &Rule::default()).is_err());
+}
+
+#[test]
+fn test_timestamped() {
+    let path = timestamped("png");
+
+    assert!(is_image(&path));
+    assert!(path.to_string_lossy().starts_with("life-"));
+}
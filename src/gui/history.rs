@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::board::Board;
+
+/// Bounded undo/redo stacks of previous boards
+pub(crate) struct History {
+    past: VecDeque<Board>,
+    future: Vec<Board>,
+    capacity: usize,
+}
+
+impl History {
+    pub(crate) fn new(capacity: usize) -> Self {
+        History {
+            past: VecDeque::with_capacity(capacity),
+            future: vec![],
+            capacity,
+        }
+    }
+
+    /// Remember the board that was just replaced, forgetting anything that was undone
+    pub(crate) fn record(&mut self, previous: Board) {
+        self.future.clear();
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(previous);
+    }
+
+    /// Remember a copy of the current board before it changes in place, reusing the oldest
+    /// remembered board's cells once full
+    pub(crate) fn record_copy(&mut self, current: &Board) {
+        self.future.clear();
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            let mut oldest = self.past.pop_front().expect("capacity is above 0");
+            oldest.clone_from(current);
+            self.past.push_back(oldest);
+        } else {
+            self.past.push_back(current.clone());
+        }
+    }
+
+    pub(crate) fn undo(&mut self, current: &mut Board) -> bool {
+        match self.past.pop_back() {
+            Some(previous) => {
+                self.future.push(mem::replace(current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn redo(&mut self, current: &mut Board) -> bool {
+        match self.future.pop() {
+            Some(next) => {
+                self.past.push_back(mem::replace(current, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undo until the board is from an earlier generation
+    pub(crate) fn step_back(&mut self, current: &mut Board) -> bool {
+        let generation = current.generation();
+        let mut moved = false;
+
+        while current.generation() >= generation && self.undo(current) {
+            moved = true;
+        }
+        moved
+    }
+
+    /// Undo or redo until reaching the first board at (or the nearest one to) `generation`
+    pub(crate) fn scrub_to(&mut self, current: &mut Board, generation: usize) {
+        while current.generation() > generation && self.undo(current) {}
+        while current.generation() < generation && self.redo(current) {}
+    }
+
+    pub(crate) fn oldest_generation(&self) -> Option<usize> {
+        self.past.front().map(Board::generation)
+    }
+
+    pub(crate) fn newest_generation(&self) -> Option<usize> {
+        self.future.first().map(Board::generation)
+    }
+
+    /// Apply `f` to every remembered board, e.g. to keep them the same size as the current one
+    pub(crate) fn update(&mut self, f: impl Fn(&Board) -> Board) {
+        for brd in self.past.iter_mut().chain(self.future.iter_mut()) {
+            *brd = f(brd);
+        }
+    }
+}
+
+#[cfg(test)]
+fn generations(capacity: usize, count: usize) -> (History, Board) {
+    let mut history = History::new(capacity);
+    let mut brd = Board::new(3, 3);
+
+    for _ in 0..count {
+        let next = brd.next_generation();
+        history.record(mem::replace(&mut brd, next));
+    }
+    (history, brd)
+}
+
+#[test]
+fn test_undo_redo() {
+    let (mut history, mut brd) = generations(10, 3);
+
+    assert!(history.undo(&mut brd));
+    assert_eq!(brd.generation(), 2);
+    assert!(history.undo(&mut brd));
+    assert!(history.redo(&mut brd));
+    assert_eq!(brd.generation(), 2);
+    assert_eq!(history.newest_generation(), Some(3));
+
+    // A new change forgets the undone boards
+    let toggled = brd.toggle(0, 0);
+    history.record(mem::replace(&mut brd, toggled));
+    assert!(!history.redo(&mut brd));
+    assert_eq!(history.newest_generation(), None);
+}
+
+#[test]
+fn test_capacity() {
+    let (mut history, mut brd) = generations(2, 5);
+
+    assert_eq!(history.oldest_generation(), Some(3));
+    assert!(history.undo(&mut brd));
+    assert!(history.undo(&mut brd));
+    assert!(!history.undo(&mut brd));
+    assert_eq!(brd.generation(), 3);
+
+    let (mut history, mut brd) = generations(0, 5);
+    assert!(!history.undo(&mut brd));
+}
+
+#[test]
+fn test_step_back() {
+    let (mut history, mut brd) = generations(10, 2);
+    let toggled = brd.toggle(0, 0);
+    history.record(mem::replace(&mut brd, toggled));
+
+    // Skips over the edit made in generation 2
+    assert!(history.step_back(&mut brd));
+    assert_eq!(brd.generation(), 1);
+    assert!(history.step_back(&mut brd));
+    assert_eq!(brd.generation(), 0);
+    assert!(!history.step_back(&mut brd));
+}
+
+#[test]
+fn test_scrub_to() {
+    let (mut history, mut brd) = generations(100, 20);
+
+    history.scrub_to(&mut brd, 5);
+    assert_eq!(brd.generation(), 5);
+    history.scrub_to(&mut brd, 12);
+    assert_eq!(brd.generation(), 12);
+    history.scrub_to(&mut brd, 99);
+    assert_eq!(brd.generation(), 20);
+    history.scrub_to(&mut brd, 0);
+    assert_eq!(brd.generation(), 0);
+}
+
+#[test]
+fn test_record_copy() {
+    let mut history = History::new(2);
+    let mut brd: Board = "...\n@@@\n...".parse().unwrap();
+
+    for _ in 0..5 {
+        history.record_copy(&brd);
+        brd.step_in_place();
+    }
+    assert_eq!(history.oldest_generation(), Some(3));
+    assert!(history.undo(&mut brd));
+    assert_eq!(brd.to_string(), "...\n@@@\n...");
+    assert_eq!(brd.generation(), 4);
+    assert!(history.undo(&mut brd));
+    assert_eq!(brd.generation(), 3);
+    assert!(!history.undo(&mut brd));
+}
@@ -0,0 +1,125 @@
+//! A tiny built-in bitmap font, so the overlay doesn't need a font file
+
+use piston_window::{math::Matrix2d, rectangle, G2d};
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+/// Window pixels per font pixel
+const PIXEL: f64 = 2.0;
+/// Font pixels around and between lines of text
+const MARGIN: usize = 2;
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const BACKDROP_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+
+/// The rows of a character's glyph, top to bottom, most significant of the 3 bits leftmost.
+/// Lowercase letters use the uppercase glyph, and unknown characters a question mark.
+#[rustfmt::skip]
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        _ => [0b111, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+/// The (x, y) font pixels lit for a line of text, starting at the origin
+fn text_pixels(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    text.chars().enumerate().flat_map(|(i, c)| {
+        glyph(c).into_iter().enumerate().flat_map(move |(y, row)| {
+            (0..GLYPH_WIDTH)
+                .filter(move |x| row & (1 << (GLYPH_WIDTH - 1 - x)) != 0)
+                .map(move |x| (i * (GLYPH_WIDTH + 1) + x, y))
+        })
+    })
+}
+
+/// Draw lines of text on a translucent backdrop in the top-left corner of the window
+pub(crate) fn draw(lines: &[String], transform: Matrix2d, g: &mut G2d) {
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        * (GLYPH_WIDTH + 1)
+        + 2 * MARGIN
+        - 1;
+    let height = lines.len() * (GLYPH_HEIGHT + MARGIN) + MARGIN;
+
+    rectangle(
+        BACKDROP_COLOR,
+        [0.0, 0.0, width as f64 * PIXEL, height as f64 * PIXEL],
+        transform,
+        g,
+    );
+    for (n, line) in lines.iter().enumerate() {
+        let top = MARGIN + n * (GLYPH_HEIGHT + MARGIN);
+        for (x, y) in text_pixels(line) {
+            rectangle(
+                TEXT_COLOR,
+                [
+                    (MARGIN + x) as f64 * PIXEL,
+                    (top + y) as f64 * PIXEL,
+                    PIXEL,
+                    PIXEL,
+                ],
+                transform,
+                g,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_text_pixels() {
+    let mut pixels: Vec<_> = text_pixels("1-").collect();
+    pixels.sort();
+
+    #[rustfmt::skip]
+    assert_eq!(pixels, [(0, 1), (0, 4), (1, 0), (1, 1), (1, 2), (1, 3), (1, 4), (2, 4), (4, 2), (5, 2), (6, 2)]);
+    assert_eq!(glyph('a'), glyph('A'));
+    assert_eq!(glyph('~'), glyph('?'));
+}
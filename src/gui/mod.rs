@@ -1,18 +1,74 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use std::{cmp::max, num::ParseFloatError};
 
 use crate::board::Board;
+use crate::color::{pixels, ColorMode, Colors};
+use crate::export::save_image;
+use crate::patterns::{Pattern, PATTERNS};
+use crate::rle::to_rle;
+use crate::stats::CellStats;
 use ::image::ImageBuffer;
 use piston_window::*;
 
+mod clipboard;
+mod files;
+mod history;
+mod hud;
 #[cfg(feature = "test_mainthread")]
 pub mod test_helper;
+mod tools;
+mod view;
 
-const LIVE_COLOR: [u8; 4] = [255, 255, 255, 255];
-const DEAD_COLOR: [u8; 4] = [0, 0, 0, 255];
+use history::History;
+use tools::{Cell, Stroke, Tool};
+use view::View;
+
+/// Generations moved by a single scrub (PageUp/PageDown)
+const SCRUB_STEP: usize = 10;
+/// Bounds on the target updates per second set with `[` and `]`
+const MIN_UPS: u64 = 1;
+const MAX_UPS: u64 = 1000;
+/// How long max speed mode spends computing generations per update
+const MAX_SPEED_BUDGET: Duration = Duration::from_millis(12);
+/// Grid lines are only drawn once cells are at least this many pixels wide
+const GRID_MIN_SCALE: f64 = 6.0;
+/// Every this many cells the grid line is thicker
+const GRID_MAJOR: usize = 10;
+/// Pixels moved by a single arrow key press
+const PAN_STEP: f64 = 32.0;
+/// Zoom factor per mouse wheel notch or `+`/`-` press
+const ZOOM_STEP: f64 = 1.25;
+/// Cells a line or rectangle will paint when the mouse button is released
+const PREVIEW_COLOR: [f32; 4] = [0.5, 0.5, 1.0, 0.6];
+const SELECTION_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 1.0];
+
+/// How the window starts out, mostly from the command line
+pub(crate) struct Settings {
+    /// Pixels per cell side
+    pub(crate) scale: f64,
+    pub(crate) ups: u64,
+    /// Start running rather than paused
+    pub(crate) running: bool,
+    pub(crate) generation_limit: Option<usize>,
+    pub(crate) exit_on_finish: bool,
+    /// Number of previous boards kept for undo
+    pub(crate) history: usize,
+    /// Generations advanced by a single `N` press
+    pub(crate) step_size: usize,
+    pub(crate) color_mode: ColorMode,
+    pub(crate) colors: Colors,
+    /// Where Ctrl+S saves and Ctrl+O loads
+    pub(crate) save_path: Option<PathBuf>,
+    /// Draw grid lines when zoomed in
+    pub(crate) grid: bool,
+}
 
 struct GameState {
     brd: Board,
-    scale: f64,
+    view: View,
+    panning: bool,
     window: PistonWindow,
     cursor: [f64; 2],
     texture_context: G2dTextureContext,
@@ -20,17 +76,51 @@ struct GameState {
     running: bool,
     generation_limit: Option<usize>,
     exit_on_finish: bool,
+    history: History,
+    tool: Tool,
+    stroke: Option<Stroke>,
+    /// Shift is held, so strokes erase
+    erasing: bool,
+    /// Ctrl (or Cmd) is held, for the clipboard shortcuts
+    ctrl: bool,
+    /// Selected cells as inclusive `(left, top, right, bottom)`
+    selection: Option<(usize, usize, usize, usize)>,
+    clipboard: Option<Board>,
+    /// A pattern following the cursor, placed with its top-left corner at the next click
+    floating: Option<Board>,
+    color_mode: ColorMode,
+    colors: Colors,
+    stats: CellStats,
+    /// Show the status overlay
+    hud: bool,
+    /// Target updates per second
+    ups: u64,
+    /// Compute as many generations per update as time allows, ignoring `ups`
+    max_speed: bool,
+    step_size: usize,
+    save_path: Option<PathBuf>,
+    /// The file most recently saved to, for Ctrl+O without a save path
+    last_saved: Option<PathBuf>,
+    grid: bool,
+    /// When each of the generations in the last second was computed
+    steps: VecDeque<Instant>,
 }
 
 impl GameState {
-    fn new(
-        brd: Board,
-        scale: f64,
-        ups: u64,
-        running: bool,
-        generation_limit: Option<usize>,
-        exit_on_finish: bool,
-    ) -> Self {
+    fn new(brd: Board, settings: Settings) -> Self {
+        let Settings {
+            scale,
+            ups,
+            running,
+            generation_limit,
+            exit_on_finish,
+            history,
+            step_size,
+            color_mode,
+            colors,
+            save_path,
+            grid,
+        } = settings;
         let mut window: PistonWindow = WindowSettings::new(
             "Life",
             [brd.cols() as f64 * scale, brd.rows() as f64 * scale],
@@ -44,8 +134,10 @@ impl GameState {
         let texture = Self::make_texture(&mut texture_context, brd.cols(), brd.rows());
 
         GameState {
+            stats: CellStats::new(&brd),
             brd,
-            scale,
+            view: View::new(scale),
+            panning: false,
             window,
             cursor: [0.0, 0.0],
             texture_context,
@@ -53,6 +145,308 @@ impl GameState {
             running,
             generation_limit,
             exit_on_finish,
+            history: History::new(history),
+            tool: Tool::Draw,
+            stroke: None,
+            erasing: false,
+            ctrl: false,
+            selection: None,
+            clipboard: None,
+            floating: None,
+            color_mode,
+            colors,
+            hud: false,
+            ups,
+            max_speed: false,
+            step_size,
+            save_path,
+            last_saved: None,
+            grid,
+            steps: VecDeque::new(),
+        }
+    }
+
+    fn paint(&mut self, cells: impl IntoIterator<Item = Cell>, state: u8) {
+        for (row, col) in cells {
+            if row >= 0 && col >= 0 {
+                self.brd.set(row as usize, col as usize, state);
+            }
+        }
+    }
+
+    fn start_stroke(&mut self) {
+        let start = self.cursor_cell();
+        if let Some(pattern) = self.floating.take() {
+            self.history.record(self.brd.clone());
+            self.brd.paste(&pattern, start.0, start.1);
+            return;
+        }
+
+        let state = match (self.erasing, self.scaled_cursor()) {
+            (true, _) => 0,
+            // Clicking toggles the first cell; the rest of the stroke follows suit
+            (false, Some((row, col))) => self.brd.next_state(self.brd.cell(row, col)),
+            (false, None) => 1,
+        };
+
+        if self.tool == Tool::Draw {
+            self.history.record(self.brd.clone());
+            self.paint([start], state);
+        }
+        self.stroke = Some(Stroke {
+            start,
+            last: start,
+            state,
+        });
+    }
+
+    fn continue_stroke(&mut self) {
+        if let Some(stroke) = self.stroke {
+            let cell = self.cursor_cell();
+            if self.tool == Tool::Draw && cell != stroke.last {
+                self.paint(tools::line(stroke.last, cell), stroke.state);
+            }
+            self.stroke = Some(Stroke {
+                last: cell,
+                ..stroke
+            });
+        }
+    }
+
+    fn finish_stroke(&mut self) {
+        if let Some(stroke) = self.stroke.take() {
+            if self.tool == Tool::Select {
+                self.select(stroke.start, stroke.last);
+                return;
+            }
+            let shape = self.tool.shape(stroke.start, stroke.last);
+            if !shape.is_empty() {
+                self.history.record(self.brd.clone());
+                self.paint(shape, stroke.state);
+            }
+        }
+    }
+
+    /// Select the cells between opposite corners `a` and `b` that lie on the board
+    fn select(&mut self, a: Cell, b: Cell) {
+        let (left, top, right, bottom) = tools::bounds(a, b);
+        let (cols, rows) = (self.brd.cols() as isize, self.brd.rows() as isize);
+
+        self.selection = (right >= 0 && bottom >= 0 && left < cols && top < rows).then(|| {
+            (
+                left.max(0) as usize,
+                top.max(0) as usize,
+                right.min(cols - 1) as usize,
+                bottom.min(rows - 1) as usize,
+            )
+        });
+    }
+
+    fn selected(&self) -> Option<Board> {
+        self.selection
+            .map(|(left, top, right, bottom)| self.brd.crop(left, top, right, bottom))
+    }
+
+    /// Copy the selection to the internal clipboard, and to the system clipboard as RLE
+    fn copy(&mut self) {
+        if let Some(pattern) = self.selected() {
+            clipboard::copy(&to_rle(&pattern));
+            self.clipboard = Some(pattern);
+        }
+    }
+
+    fn cut(&mut self) {
+        self.copy();
+        if let (Some(pattern), Some((left, top, ..))) = (&self.clipboard, self.selection) {
+            let mut brd = self.brd.clone();
+            brd.paste(&pattern.clear(), top as isize, left as isize);
+            self.set_board(brd);
+        }
+    }
+
+    /// Rotate or flip the floating pattern, first lifting a copy of the selection if there
+    /// isn't one
+    fn transform(&mut self, f: fn(&Board) -> Board) {
+        if self.floating.is_none() {
+            self.floating = self.selected();
+        }
+        self.floating = self.floating.as_ref().map(f);
+    }
+
+    /// Float the built-in pattern for a number key at the cursor, ready to stamp
+    fn pick_pattern(&mut self, key: Key) {
+        // `1` picks the first pattern and `0` the tenth
+        let index = (key as usize + 9 - Key::D0 as usize) % 10;
+
+        self.floating = PATTERNS.get(index).map(Pattern::board);
+    }
+
+    /// Save to the save path, or a new timestamped file; images are snapshots of the window's
+    /// colors at one pixel per cell
+    fn save(&mut self, path: Option<PathBuf>) {
+        let path = path.unwrap_or_else(|| files::timestamped("rle"));
+        let saved = if files::is_image(&path) {
+            save_image(
+                &self.brd,
+                &self.stats,
+                self.color_mode,
+                &self.colors,
+                1,
+                &path,
+            )
+            .map_err(|err| err.to_string())
+        } else {
+            files::save(&self.brd, &path).map_err(|err| err.to_string())
+        };
+
+        match saved {
+            Ok(()) if files::is_image(&path) => {}
+            Ok(()) => self.last_saved = Some(path),
+            Err(err) => eprintln!("Couldn't save: {err}"),
+        }
+    }
+
+    /// Replace the board with one from a file, growing the board to fit it if needed
+    fn load(&mut self, path: &Path) {
+        match files::load(path, self.brd.rule()) {
+            Ok(brd) => {
+                let brd = brd.with_engine(self.brd.engine().clone());
+                self.grow_to(brd.cols(), brd.rows());
+                self.set_board(Self::pad_to(&brd, self.brd.cols(), self.brd.rows()));
+                self.running = false;
+            }
+            Err(err) => eprintln!("Couldn't load: {err}"),
+        }
+    }
+
+    /// Compute the next generation in place, remembering a copy of the old one for undo
+    fn advance(&mut self) {
+        self.history.record_copy(&self.brd);
+        self.brd.step_in_place();
+        self.stats.update(&self.brd);
+        self.steps.push_back(Instant::now());
+    }
+
+    /// Advance while running: one generation per update, or as many as fit in
+    /// `MAX_SPEED_BUDGET` at max speed, stopping at the generation limit
+    fn run_generations(&mut self) {
+        let start = Instant::now();
+
+        loop {
+            if Some(self.brd.generation()) == self.generation_limit {
+                if self.exit_on_finish {
+                    self.window.set_should_close(true);
+                } else {
+                    self.running = false;
+                }
+                return;
+            }
+            self.advance();
+            if !self.max_speed || start.elapsed() >= MAX_SPEED_BUDGET {
+                return;
+            }
+        }
+    }
+
+    fn set_ups(&mut self, ups: u64) {
+        self.ups = ups.clamp(MIN_UPS, MAX_UPS);
+        self.window.set_ups(self.ups);
+    }
+
+    /// The target speed, for display
+    fn target_ups(&self) -> String {
+        if self.max_speed {
+            "max".to_string()
+        } else {
+            self.ups.to_string()
+        }
+    }
+
+    /// Generations actually computed over the last second
+    fn measured_ups(&mut self) -> usize {
+        let now = Instant::now();
+        while self
+            .steps
+            .front()
+            .is_some_and(|&step| now - step > Duration::from_secs(1))
+        {
+            self.steps.pop_front();
+        }
+        self.steps.len()
+    }
+
+    /// The cell under the cursor, if it's on the board
+    fn hovered_cell(&self) -> Option<(usize, usize)> {
+        self.scaled_cursor()
+            .filter(|&(row, col)| row < self.brd.rows() && col < self.brd.cols())
+    }
+
+    fn hud_lines(&mut self) -> Vec<String> {
+        vec![
+            format!(
+                "GEN {}  POP {}",
+                self.brd.generation(),
+                self.brd.population()
+            ),
+            format!("UPS {}/{}", self.measured_ups(), self.target_ups()),
+            format!("RULE {}", self.brd.rule()),
+            match self.hovered_cell() {
+                Some((row, col)) => format!("CELL {row},{col}"),
+                None => "CELL -".to_string(),
+            },
+        ]
+    }
+
+    fn title(&mut self) -> String {
+        format!(
+            "Life - generation {}, population {}, {}/{} ups, {}{}",
+            self.brd.generation(),
+            self.brd.population(),
+            self.measured_ups(),
+            self.target_ups(),
+            self.brd.rule(),
+            self.hovered_cell()
+                .map_or(String::new(), |(row, col)| format!(", cell ({row}, {col})"))
+        )
+    }
+
+    fn set_tool(&mut self, tool: Tool) {
+        self.tool = tool;
+        self.floating = None;
+    }
+
+    /// Replace the board, remembering the old one for undo
+    fn set_board(&mut self, brd: Board) {
+        self.history.record(std::mem::replace(&mut self.brd, brd));
+    }
+
+    fn scrub_to(&mut self, generation: usize) {
+        self.running = false;
+        self.history.scrub_to(&mut self.brd, generation);
+    }
+
+    fn window_center(&self) -> [f64; 2] {
+        let size = self.window.size();
+        [size.width / 2.0, size.height / 2.0]
+    }
+
+    /// Zoom and pan so the cells from `(left, top)` to `(right, bottom)` fill the window
+    fn zoom_to(&mut self, left: usize, top: usize, right: usize, bottom: usize) {
+        let size = self.window.size();
+        self.view
+            .zoom_to([size.width, size.height], (left, top, right, bottom));
+    }
+
+    fn fit_to_window(&mut self) {
+        let (cols, rows) = (self.brd.cols(), self.brd.rows());
+        if cols > 0 && rows > 0 {
+            self.zoom_to(0, 0, cols - 1, rows - 1);
+        }
+    }
+
+    fn zoom_to_content(&mut self) {
+        if let Some((left, top, right, bottom)) = self.brd.bounding_box() {
+            self.zoom_to(left, top, right, bottom);
         }
     }
 
@@ -77,32 +471,130 @@ impl GameState {
 
     fn handle_event(&mut self, e: Event) {
         e.mouse_cursor(|xy| {
+            if self.panning {
+                self.view.offset[0] += xy[0] - self.cursor[0];
+                self.view.offset[1] += xy[1] - self.cursor[1];
+            }
             self.cursor = xy;
+            self.continue_stroke();
         });
 
+        if let Some([_, notches]) = e.mouse_scroll_args() {
+            self.view.zoom_at(ZOOM_STEP.powf(notches), self.cursor);
+        }
+
+        match e.release_args() {
+            Some(Button::Mouse(MouseButton::Middle)) => self.panning = false,
+            Some(Button::Mouse(MouseButton::Left)) => self.finish_stroke(),
+            Some(Button::Keyboard(Key::LShift | Key::RShift)) => self.erasing = false,
+            Some(Button::Keyboard(Key::LCtrl | Key::RCtrl | Key::LGui | Key::RGui)) => {
+                self.ctrl = false
+            }
+            _ => {}
+        }
+
         if let Some(btn) = e.press_args() {
             match btn {
-                Button::Mouse(MouseButton::Left) => {
-                    let (x, y) = self.scaled_cursor();
-                    self.brd = self.brd.toggle(x, y);
+                Button::Mouse(MouseButton::Left) => self.start_stroke(),
+                Button::Keyboard(Key::LShift | Key::RShift) => self.erasing = true,
+                Button::Keyboard(Key::LCtrl | Key::RCtrl | Key::LGui | Key::RGui) => {
+                    self.ctrl = true
                 }
+                Button::Keyboard(Key::C) if self.ctrl => self.copy(),
+                Button::Keyboard(Key::X) if self.ctrl => self.cut(),
+                Button::Keyboard(Key::V) if self.ctrl => self.floating = self.clipboard.clone(),
+                Button::Keyboard(Key::S) if self.ctrl => self.save(self.save_path.clone()),
+                Button::Keyboard(Key::O) if self.ctrl => {
+                    if let Some(path) = self.save_path.clone().or(self.last_saved.clone()) {
+                        self.load(&path)
+                    }
+                }
+                Button::Keyboard(Key::P) => self.save(Some(files::timestamped("png"))),
+                Button::Keyboard(
+                    key @ (Key::D1
+                    | Key::D2
+                    | Key::D3
+                    | Key::D4
+                    | Key::D5
+                    | Key::D6
+                    | Key::D7
+                    | Key::D8
+                    | Key::D9
+                    | Key::D0),
+                ) => self.pick_pattern(key),
+                Button::Keyboard(Key::O) => self.transform(Board::rotate_clockwise),
+                Button::Keyboard(Key::H) => self.transform(Board::flip_horizontal),
+                Button::Keyboard(Key::V) => self.transform(Board::flip_vertical),
+                Button::Keyboard(Key::D) => self.set_tool(Tool::Draw),
+                Button::Keyboard(Key::L) => self.set_tool(Tool::Line),
+                Button::Keyboard(Key::T) => self.set_tool(Tool::Rectangle),
+                Button::Keyboard(Key::A) => self.set_tool(Tool::Select),
+                Button::Mouse(MouseButton::Middle) => self.panning = true,
+                Button::Keyboard(Key::Left) => self.view.offset[0] += PAN_STEP,
+                Button::Keyboard(Key::Right) => self.view.offset[0] -= PAN_STEP,
+                Button::Keyboard(Key::Up) => self.view.offset[1] += PAN_STEP,
+                Button::Keyboard(Key::Down) => self.view.offset[1] -= PAN_STEP,
+                Button::Keyboard(Key::Equals | Key::NumPadPlus) => {
+                    self.view.zoom_at(ZOOM_STEP, self.window_center())
+                }
+                Button::Keyboard(Key::Minus | Key::NumPadMinus) => {
+                    self.view.zoom_at(1.0 / ZOOM_STEP, self.window_center())
+                }
+                Button::Keyboard(Key::F) => self.fit_to_window(),
+                Button::Keyboard(Key::I) => self.hud = !self.hud,
+                Button::Keyboard(Key::G) => self.grid = !self.grid,
+                Button::Keyboard(Key::E) => self.zoom_to_content(),
                 Button::Mouse(MouseButton::Right) | Button::Keyboard(Key::Space) => {
                     self.running = !self.running;
                 }
-                Button::Keyboard(Key::C) => self.brd = self.brd.clear(),
+                Button::Keyboard(Key::C) => self.set_board(self.brd.clear()),
                 Button::Keyboard(Key::Q) => self.window.set_should_close(true),
-                Button::Keyboard(Key::R) => self.brd = self.brd.random(),
-                Button::Keyboard(Key::S) => self.brd = self.brd.next_generation(),
+                Button::Keyboard(Key::R) => self.set_board(self.brd.random()),
+                Button::Keyboard(Key::S) => self.advance(),
+                Button::Keyboard(Key::N) => {
+                    for _ in 0..self.step_size {
+                        self.advance();
+                    }
+                }
+                Button::Keyboard(Key::LeftBracket) => self.set_ups(self.ups / 2),
+                Button::Keyboard(Key::RightBracket) => self.set_ups(self.ups * 2),
+                Button::Keyboard(Key::M) => self.max_speed = !self.max_speed,
+                Button::Keyboard(Key::Z) => {
+                    self.running = false;
+                    self.history.undo(&mut self.brd);
+                }
+                Button::Keyboard(Key::Y) => {
+                    self.running = false;
+                    self.history.redo(&mut self.brd);
+                }
+                Button::Keyboard(Key::B) => {
+                    self.running = false;
+                    self.history.step_back(&mut self.brd);
+                }
+                Button::Keyboard(Key::PageUp) => {
+                    self.scrub_to(self.brd.generation().saturating_sub(SCRUB_STEP))
+                }
+                Button::Keyboard(Key::PageDown) => {
+                    self.scrub_to(self.brd.generation() + SCRUB_STEP)
+                }
+                Button::Keyboard(Key::Home) => {
+                    if let Some(generation) = self.history.oldest_generation() {
+                        self.scrub_to(generation)
+                    }
+                }
+                Button::Keyboard(Key::End) => {
+                    if let Some(generation) = self.history.newest_generation() {
+                        self.scrub_to(generation)
+                    }
+                }
                 _ => {}
             };
         }
 
         if e.render_args().is_some() {
-            let cells = self
-                .brd
-                .iter()
-                .flat_map(|&val| if val { LIVE_COLOR } else { DEAD_COLOR })
-                .collect();
+            // Catch up with edits, undo and scrubbing
+            self.stats.update(&self.brd);
+            let cells = pixels(&self.brd, &self.stats, self.color_mode, &self.colors);
 
             self.texture
                 .update(
@@ -111,68 +603,193 @@ impl GameState {
                         .unwrap(),
                 )
                 .unwrap();
+            let mut preview = self
+                .stroke
+                .map(|stroke| self.tool.shape(stroke.start, stroke.last))
+                .unwrap_or_default();
+            preview.extend(self.floating_cells());
+            let outline = self.outline();
+            let title = self.title();
+            if title != self.window.get_title() {
+                self.window.set_title(title);
+            }
+            let hud = self.hud.then(|| self.hud_lines());
+            let grid = self.grid_lines();
+            let [r, g, b] = self.colors.grid.map(|c| c as f32 / 255.0);
+            let grid_color = [r, g, b, 1.0];
+            let [r, g, b] = self.colors.background.map(|c| c as f32 / 255.0);
+            let background = [r, g, b, 1.0];
             self.window.draw_2d(&e, |c, g, d| {
-                image(&self.texture, c.transform.scale(self.scale, self.scale), g);
+                let transform = c
+                    .transform
+                    .trans(self.view.offset[0], self.view.offset[1])
+                    .scale(self.view.scale, self.view.scale);
+                clear(background, g);
+                image(&self.texture, transform, g);
+                for (width, coords) in grid {
+                    line(
+                        grid_color,
+                        width / 2.0 / self.view.scale,
+                        coords,
+                        transform,
+                        g,
+                    );
+                }
+                for (row, col) in preview {
+                    rectangle(
+                        PREVIEW_COLOR,
+                        [col as f64, row as f64, 1.0, 1.0],
+                        transform,
+                        g,
+                    );
+                }
+                if let Some(rect) = outline {
+                    Rectangle::new_border(SELECTION_COLOR, 1.0 / self.view.scale).draw(
+                        rect,
+                        &c.draw_state,
+                        transform,
+                        g,
+                    );
+                }
+                if let Some(lines) = hud {
+                    hud::draw(&lines, c.transform, g);
+                }
                 self.texture_context.encoder.flush(d);
             });
         }
 
         if e.update_args().is_some() && self.running {
-            if Some(self.brd.generation()) == self.generation_limit {
-                if self.exit_on_finish {
-                    self.window.set_should_close(true);
-                } else {
-                    self.running = false;
-                }
-            } else {
-                self.brd = self.brd.next_generation();
-            }
+            self.run_generations();
         }
 
         if let Some(r) = e.resize_args() {
-            let (old_cols, old_rows) = (self.brd.cols(), self.brd.rows());
-            let (cols, rows) = (
-                max(old_cols, self.scale_dimension(r.window_size[0])),
-                max(old_rows, self.scale_dimension(r.window_size[1])),
-            );
-            if cols != old_cols || rows != old_rows {
-                self.brd =
-                    self.brd
-                        .pad(0, (cols - old_cols) as isize, (rows - old_rows) as isize, 0);
-                self.texture = Self::make_texture(&mut self.texture_context, cols, rows);
-            }
+            let (cols, rows) = self.view.cells_to_fill(r.window_size);
+            self.grow_to(cols, rows);
+        }
+    }
+
+    /// Grow the board (and every board in history) to at least `cols`×`rows`
+    fn grow_to(&mut self, cols: usize, rows: usize) {
+        let (old_cols, old_rows) = (self.brd.cols(), self.brd.rows());
+        let (cols, rows) = (max(old_cols, cols), max(old_rows, rows));
+
+        if cols != old_cols || rows != old_rows {
+            self.brd = Self::pad_to(&self.brd, cols, rows);
+            self.history.update(|brd| Self::pad_to(brd, cols, rows));
+            self.texture = Self::make_texture(&mut self.texture_context, cols, rows);
+        }
+    }
+
+    /// The `(width in pixels, [x1, y1, x2, y2] in cells)` of each visible grid line, if the grid
+    /// is on and cells are big enough for it
+    fn grid_lines(&self) -> Vec<(f64, [f64; 4])> {
+        if !self.grid || self.view.scale < GRID_MIN_SCALE {
+            return vec![];
         }
+        let size = self.window.size();
+        // The visible range of cell boundaries along one axis
+        let visible = |offset: f64, extent: f64, cells: usize| {
+            let first = (-offset / self.view.scale).floor().clamp(0.0, cells as f64) as usize;
+            let last = ((extent - offset) / self.view.scale)
+                .ceil()
+                .clamp(0.0, cells as f64) as usize;
+            first..=last
+        };
+        let (cols, rows) = (
+            visible(self.view.offset[0], size.width, self.brd.cols()),
+            visible(self.view.offset[1], size.height, self.brd.rows()),
+        );
+        let width = |n: usize| {
+            if n.is_multiple_of(GRID_MAJOR) {
+                2.0
+            } else {
+                1.0
+            }
+        };
+        let (top, bottom) = (*rows.start() as f64, *rows.end() as f64);
+        let (left, right) = (*cols.start() as f64, *cols.end() as f64);
+
+        cols.map(|col| (width(col), [col as f64, top, col as f64, bottom]))
+            .chain(rows.map(|row| (width(row), [left, row as f64, right, row as f64])))
+            .collect()
+    }
+
+    /// The live cells of the floating pattern, placed at the cursor
+    fn floating_cells(&self) -> Vec<Cell> {
+        let (row, col) = self.cursor_cell();
+
+        self.floating
+            .iter()
+            .flat_map(|pattern| {
+                pattern
+                    .states()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &state)| state != 0)
+                    .map(move |(i, _)| {
+                        (
+                            row + (i / pattern.cols()) as isize,
+                            col + (i % pattern.cols()) as isize,
+                        )
+                    })
+            })
+            .collect()
     }
 
-    fn scale_dimension(&self, x: f64) -> usize {
-        (x / self.scale).floor() as usize
+    /// The `[x, y, width, height]` (in cells) to outline: the floating pattern, the selection
+    /// being dragged out, or the current selection
+    fn outline(&self) -> Option<[f64; 4]> {
+        let (left, top, right, bottom) = match (&self.floating, self.stroke) {
+            (Some(pattern), _) => {
+                let (row, col) = self.cursor_cell();
+                let (rows, cols) = (pattern.rows() as isize, pattern.cols() as isize);
+                (col, row, col + cols - 1, row + rows - 1)
+            }
+            (None, Some(stroke)) if self.tool == Tool::Select => {
+                tools::bounds(stroke.start, stroke.last)
+            }
+            (None, _) => {
+                let (left, top, right, bottom) = self.selection?;
+                (left as isize, top as isize, right as isize, bottom as isize)
+            }
+        };
+
+        Some([
+            left as f64,
+            top as f64,
+            (right + 1 - left) as f64,
+            (bottom + 1 - top) as f64,
+        ])
     }
 
-    fn scaled_cursor(&self) -> (usize, usize) {
-        (
-            self.scale_dimension(self.cursor[1]),
-            self.scale_dimension(self.cursor[0]),
+    /// Grow a board to at least `cols`×`rows`, adding dead cells on the right and bottom
+    fn pad_to(brd: &Board, cols: usize, rows: usize) -> Board {
+        brd.pad(
+            0,
+            cols.saturating_sub(brd.cols()) as isize,
+            rows.saturating_sub(brd.rows()) as isize,
+            0,
         )
     }
+
+    /// The (row, column) of the cell under the cursor, which may be off the board
+    fn cursor_cell(&self) -> Cell {
+        let cell =
+            |i: usize| ((self.cursor[i] - self.view.offset[i]) / self.view.scale).floor() as isize;
+
+        (cell(1), cell(0))
+    }
+
+    /// The (row, column) of the cell under the cursor, if it's over the board
+    fn scaled_cursor(&self) -> Option<(usize, usize)> {
+        let (row, col) = self.cursor_cell();
+
+        (row >= 0 && col >= 0).then_some((row as usize, col as usize))
+    }
 }
 
-pub fn run(
-    brd: Board,
-    scale: f64,
-    ups: u64,
-    init_running: bool,
-    generation_limit: Option<usize>,
-    exit_on_finish: bool,
-) {
-    GameState::new(
-        brd,
-        scale,
-        ups,
-        init_running,
-        generation_limit,
-        exit_on_finish,
-    )
-    .run();
+pub fn run(brd: Board, settings: Settings) {
+    GameState::new(brd, settings).run();
 }
 
 const MIN_SCALE: f64 = 0.1;
@@ -204,6 +821,6 @@ fn test_valid_scale() {
     );
     assert_eq!(
         valid_scale("puppies"),
-        Err(format!("invalid float literal"))
+        Err("invalid float literal".to_string())
     );
 }
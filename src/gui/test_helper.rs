@@ -1,13 +1,15 @@
 use std::str::FromStr;
 
 use piston_window::{
-    Button, ButtonArgs, ButtonState, Event, Input, Key, Loop, Motion::MouseCursor, MouseButton,
-    ResizeArgs, UpdateArgs, Window,
+    Button, ButtonArgs, ButtonState, Event, Input, Key, Loop,
+    Motion::{MouseCursor, MouseScroll},
+    MouseButton, ResizeArgs, UpdateArgs, Window,
 };
 
 use crate::board::Board;
+use crate::color::{ColorMode, Theme};
 
-use super::GameState;
+use super::{GameState, Settings};
 
 type Example<'a> = (&'a str, fn() -> ());
 
@@ -26,10 +28,50 @@ pub const EXAMPLES: &[Example] = &test_examples![
     test_toggle_running_event,
     test_update_event,
     test_resize_event,
+    test_resize_zoomed_out_event,
+    test_undo_redo_event,
+    test_step_back_event,
+    test_scrub_event,
+    test_click_outside_board,
+    test_scroll_zoom_event,
+    test_key_zoom_event,
+    test_pan_event,
+    test_drag_pan_event,
+    test_fit_event,
+    test_zoom_to_content_event,
+    test_drag_paint_event,
+    test_drag_erase_event,
+    test_line_tool_event,
+    test_rectangle_tool_event,
+    test_select_copy_paste_event,
+    test_cut_event,
+    test_transform_selection_event,
+    test_stamp_pattern_event,
+    test_hud_event,
+    test_speed_event,
+    test_step_n_event,
+    test_max_speed_event,
+    test_save_load_event,
+    test_grid_event,
 ];
 
 fn make_gamestate(brd: Board) -> GameState {
-    GameState::new(brd, 4.0, 1, true, Some(1), false)
+    GameState::new(
+        brd,
+        Settings {
+            scale: 4.0,
+            ups: 1,
+            running: true,
+            generation_limit: Some(1),
+            exit_on_finish: false,
+            history: 10,
+            step_size: 10,
+            color_mode: ColorMode::State,
+            colors: Theme::Dark.colors(),
+            save_path: None,
+            grid: false,
+        },
+    )
 }
 
 fn mouse_move_event(x: f64, y: f64) -> Event {
@@ -45,6 +87,19 @@ fn button_event<T: Into<Button>>(button: T) -> Event {
     .into()
 }
 
+fn button_release_event<T: Into<Button>>(button: T) -> Event {
+    Input::Button(ButtonArgs {
+        state: ButtonState::Release,
+        button: button.into(),
+        scancode: None,
+    })
+    .into()
+}
+
+fn scroll_event(notches: f64) -> Event {
+    Input::Move(MouseScroll([0.0, notches])).into()
+}
+
 fn update_event() -> Event {
     Loop::Update(UpdateArgs { dt: 0.0 }).into()
 }
@@ -167,3 +222,415 @@ fn test_resize_event() {
     // we don't truncate the board if the window shrinks
     assert_eq!(gs.brd.len(), 250);
 }
+
+fn test_resize_zoomed_out_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    for _ in 0..20 {
+        gs.handle_event(button_event(Key::Minus));
+    }
+    gs.handle_event(resize_event(40.0, 100.0));
+
+    // The board grows by the scale the window opened at, not the zoomed out one
+    assert!(gs.view.scale < 1.0);
+    assert_eq!((gs.brd.cols(), gs.brd.rows()), (10, 25));
+}
+
+fn test_undo_redo_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(MouseButton::Left));
+    gs.handle_event(button_event(Key::R));
+    gs.handle_event(button_event(Key::Z));
+
+    assert_eq!(gs.brd.to_string(), "@..\n...\n...");
+    assert!(!gs.running);
+
+    gs.handle_event(button_event(Key::Z));
+
+    assert_eq!(gs.brd.population(), 0);
+
+    gs.handle_event(button_event(Key::Y));
+
+    assert_eq!(gs.brd.to_string(), "@..\n...\n...");
+}
+
+fn test_step_back_event() {
+    let mut gs = make_gamestate(Board::from_str("...\n@@@\n...").unwrap());
+
+    gs.handle_event(button_event(Key::S));
+    gs.handle_event(button_event(Key::S));
+    gs.handle_event(button_event(Key::B));
+
+    assert_eq!(gs.brd.generation(), 1);
+    assert_eq!(gs.brd.to_string(), ".@.\n.@.\n.@.");
+}
+
+fn test_scrub_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    for _ in 0..5 {
+        gs.handle_event(button_event(Key::S));
+    }
+    gs.handle_event(button_event(Key::Home));
+
+    assert_eq!(gs.brd.generation(), 0);
+
+    gs.handle_event(button_event(Key::PageDown));
+
+    assert_eq!(gs.brd.generation(), 5);
+
+    gs.handle_event(button_event(Key::PageUp));
+
+    assert_eq!(gs.brd.generation(), 0);
+
+    gs.handle_event(button_event(Key::End));
+
+    assert_eq!(gs.brd.generation(), 5);
+}
+
+fn test_click_outside_board() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::Left));
+    gs.handle_event(button_event(MouseButton::Left));
+
+    assert_eq!(gs.brd.population(), 0);
+}
+
+fn test_scroll_zoom_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(mouse_move_event(6.0, 6.0));
+    gs.handle_event(scroll_event(1.0));
+
+    assert!(gs.view.scale > 4.0);
+    // The cell under the cursor doesn't move
+    assert_eq!(gs.scaled_cursor(), Some((1, 1)));
+
+    gs.handle_event(scroll_event(-2.0));
+
+    assert!(gs.view.scale < 4.0);
+    assert_eq!(gs.scaled_cursor(), Some((1, 1)));
+}
+
+fn test_key_zoom_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::Equals));
+
+    assert!(gs.view.scale > 4.0);
+
+    gs.handle_event(button_event(Key::Minus));
+    gs.handle_event(button_event(Key::Minus));
+
+    assert!(gs.view.scale < 4.0);
+}
+
+fn test_pan_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(mouse_move_event(1.0, 1.0));
+    assert_eq!(gs.scaled_cursor(), Some((0, 0)));
+
+    gs.handle_event(button_event(Key::Left));
+    gs.handle_event(button_event(Key::Up));
+
+    assert_eq!(gs.view.offset, [32.0, 32.0]);
+    assert_eq!(gs.scaled_cursor(), None);
+
+    gs.handle_event(button_event(Key::Right));
+    gs.handle_event(button_event(Key::Down));
+
+    assert_eq!(gs.view.offset, [0.0, 0.0]);
+}
+
+fn test_drag_pan_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(mouse_move_event(1.0, 1.0));
+    gs.handle_event(button_event(MouseButton::Middle));
+    gs.handle_event(mouse_move_event(5.0, 9.0));
+    gs.handle_event(button_release_event(MouseButton::Middle));
+    gs.handle_event(mouse_move_event(0.0, 0.0));
+
+    assert_eq!(gs.view.offset, [4.0, 8.0]);
+}
+
+fn test_fit_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+    let size = gs.window.size();
+
+    gs.handle_event(scroll_event(3.0));
+    gs.handle_event(button_event(Key::F));
+
+    assert_eq!(gs.view.scale, (size.width / 3.0).min(size.height / 3.0));
+}
+
+fn test_zoom_to_content_event() {
+    let mut gs = make_gamestate(Board::from_str("...\n...\n..@").unwrap());
+    let size = gs.window.size();
+
+    gs.handle_event(button_event(Key::E));
+
+    assert_eq!(gs.view.scale, size.width.min(size.height).min(100.0));
+    gs.handle_event(mouse_move_event(size.width / 2.0, size.height / 2.0));
+    assert_eq!(gs.scaled_cursor(), Some((2, 2)));
+}
+
+fn drag(gs: &mut GameState, from: [f64; 2], to: [f64; 2]) {
+    gs.handle_event(mouse_move_event(from[0], from[1]));
+    gs.handle_event(button_event(MouseButton::Left));
+    gs.handle_event(mouse_move_event(to[0], to[1]));
+    gs.handle_event(button_release_event(MouseButton::Left));
+}
+
+fn test_drag_paint_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    // One motion event across the board still paints every cell between
+    drag(&mut gs, [1.0, 1.0], [9.0, 9.0]);
+
+    assert_eq!(gs.brd.to_string(), "@..\n.@.\n..@");
+
+    gs.handle_event(button_event(Key::Z));
+
+    assert_eq!(gs.brd.population(), 0);
+}
+
+fn test_drag_erase_event() {
+    let mut gs = make_gamestate(Board::from_str("@@@\n@@@\n@@@").unwrap());
+
+    gs.handle_event(button_event(Key::LShift));
+    drag(&mut gs, [1.0, 5.0], [9.0, 5.0]);
+    gs.handle_event(button_release_event(Key::LShift));
+
+    assert_eq!(gs.brd.to_string(), "@@@\n...\n@@@");
+
+    // Without shift, a stroke starting on a live cell erases too
+    drag(&mut gs, [5.0, 1.0], [5.0, 9.0]);
+
+    assert_eq!(gs.brd.to_string(), "@.@\n...\n@.@");
+}
+
+fn test_line_tool_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::L));
+    gs.handle_event(mouse_move_event(1.0, 9.0));
+    gs.handle_event(button_event(MouseButton::Left));
+    gs.handle_event(mouse_move_event(5.0, 5.0));
+    gs.handle_event(mouse_move_event(9.0, 9.0));
+
+    // Nothing is painted until the button is released
+    assert_eq!(gs.brd.population(), 0);
+
+    gs.handle_event(button_release_event(MouseButton::Left));
+
+    assert_eq!(gs.brd.to_string(), "...\n...\n@@@");
+}
+
+fn test_rectangle_tool_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::T));
+    drag(&mut gs, [1.0, 1.0], [9.0, 9.0]);
+
+    assert_eq!(gs.brd.to_string(), "@@@\n@.@\n@@@");
+
+    gs.handle_event(button_event(Key::D));
+    drag(&mut gs, [5.0, 5.0], [5.0, 5.0]);
+
+    assert_eq!(gs.brd.to_string(), "@@@\n@@@\n@@@");
+}
+
+fn press_with_ctrl(gs: &mut GameState, key: Key) {
+    gs.handle_event(button_event(Key::LCtrl));
+    gs.handle_event(button_event(key));
+    gs.handle_event(button_release_event(Key::LCtrl));
+}
+
+fn test_select_copy_paste_event() {
+    let mut gs = make_gamestate(Board::from_str("@@.\n...\n...").unwrap());
+
+    gs.handle_event(button_event(Key::A));
+    drag(&mut gs, [1.0, 1.0], [5.0, 1.0]);
+
+    // Selecting doesn't change the board
+    assert_eq!(gs.selection, Some((0, 0, 1, 0)));
+    assert_eq!(gs.brd.population(), 2);
+
+    press_with_ctrl(&mut gs, Key::C);
+    press_with_ctrl(&mut gs, Key::V);
+    gs.handle_event(mouse_move_event(5.0, 5.0));
+
+    // Nothing is placed until the click
+    assert_eq!(gs.brd.population(), 2);
+
+    gs.handle_event(button_event(MouseButton::Left));
+    gs.handle_event(button_release_event(MouseButton::Left));
+
+    assert_eq!(gs.brd.to_string(), "@@.\n.@@\n...");
+    assert_eq!(gs.floating, None);
+
+    gs.handle_event(button_event(Key::Z));
+
+    assert_eq!(gs.brd.to_string(), "@@.\n...\n...");
+
+    // Choosing a tool drops a pattern waiting to be pasted
+    press_with_ctrl(&mut gs, Key::V);
+    gs.handle_event(button_event(Key::D));
+
+    assert_eq!(gs.floating, None);
+}
+
+fn test_cut_event() {
+    let mut gs = make_gamestate(Board::from_str("@@@\n@@@\n@@@").unwrap());
+
+    gs.handle_event(button_event(Key::A));
+    drag(&mut gs, [5.0, 5.0], [20.0, 20.0]);
+    press_with_ctrl(&mut gs, Key::X);
+
+    assert_eq!(gs.selection, Some((1, 1, 2, 2)));
+    assert_eq!(gs.brd.to_string(), "@@@\n@..\n@..");
+    assert_eq!(gs.clipboard.as_ref().unwrap().to_string(), "@@\n@@");
+}
+
+fn test_transform_selection_event() {
+    let mut gs = make_gamestate(Board::from_str("@@.\n...\n...").unwrap());
+
+    gs.handle_event(button_event(Key::A));
+    drag(&mut gs, [1.0, 1.0], [5.0, 1.0]);
+    gs.handle_event(button_event(Key::O));
+
+    assert_eq!(gs.floating.as_ref().unwrap().to_string(), "@\n@");
+
+    gs.handle_event(mouse_move_event(9.0, 1.0));
+    gs.handle_event(button_event(MouseButton::Left));
+
+    assert_eq!(gs.brd.to_string(), "@@@\n..@\n...");
+}
+
+fn test_stamp_pattern_event() {
+    let mut gs = make_gamestate(Board::new(5, 5));
+
+    gs.handle_event(mouse_move_event(5.0, 5.0));
+    gs.handle_event(button_event(Key::D1));
+    gs.handle_event(button_event(Key::O));
+    gs.handle_event(button_event(MouseButton::Left));
+
+    assert_eq!(gs.brd.to_string(), ".....\n.@...\n.@.@.\n.@@..\n.....");
+
+    gs.handle_event(button_event(Key::D0));
+
+    assert_eq!(gs.floating.as_ref().unwrap().to_string(), "@@@");
+}
+
+fn test_hud_event() {
+    let mut gs = make_gamestate(Board::from_str("...\n@@@\n...").unwrap());
+
+    assert!(!gs.hud);
+    gs.handle_event(button_event(Key::I));
+    assert!(gs.hud);
+
+    gs.handle_event(update_event());
+    gs.handle_event(mouse_move_event(5.0, 1.0));
+
+    assert_eq!(
+        gs.hud_lines(),
+        ["GEN 1  POP 3", "UPS 1/1", "RULE B3/S23", "CELL 0,1"]
+    );
+    assert_eq!(
+        gs.title(),
+        "Life - generation 1, population 3, 1/1 ups, B3/S23, cell (0, 1)"
+    );
+
+    gs.handle_event(mouse_move_event(100.0, 100.0));
+
+    assert_eq!(gs.hud_lines()[3], "CELL -");
+}
+
+fn test_speed_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::RightBracket));
+    gs.handle_event(button_event(Key::RightBracket));
+
+    assert_eq!(gs.ups, 4);
+
+    for _ in 0..5 {
+        gs.handle_event(button_event(Key::LeftBracket));
+    }
+
+    assert_eq!(gs.ups, 1);
+}
+
+fn test_step_n_event() {
+    let mut gs = make_gamestate(Board::from_str("...\n@@@\n...").unwrap());
+
+    gs.handle_event(button_event(Key::N));
+
+    assert_eq!(gs.brd.generation(), 10);
+    assert_eq!(gs.brd.to_string(), "...\n@@@\n...");
+}
+
+fn test_max_speed_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::M));
+    gs.handle_event(update_event());
+
+    // Stops at the generation limit even at max speed
+    assert_eq!(gs.brd.generation(), 1);
+    assert_eq!(gs.hud_lines()[1], "UPS 1/max");
+
+    gs.generation_limit = None;
+    gs.running = true;
+    gs.handle_event(update_event());
+
+    assert!(gs.brd.generation() > 2);
+}
+
+fn test_save_load_event() {
+    let path = std::env::temp_dir().join(format!("rust-life-gui-{}.rle", std::process::id()));
+    let mut gs = make_gamestate(Board::from_str(".@.\n.@.\n.@.").unwrap());
+    gs.save_path = Some(path.clone());
+
+    gs.handle_event(button_event(Key::S));
+    press_with_ctrl(&mut gs, Key::S);
+    gs.handle_event(button_event(Key::C));
+    press_with_ctrl(&mut gs, Key::O);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(gs.brd.to_string(), "...\n@@@\n...");
+    assert_eq!(gs.brd.generation(), 1);
+
+    // Loading can be undone
+    gs.handle_event(button_event(Key::Z));
+
+    assert_eq!(gs.brd.population(), 0);
+}
+
+fn test_grid_event() {
+    let mut gs = make_gamestate(Board::new(3, 3));
+
+    gs.handle_event(button_event(Key::G));
+
+    // Too small to bother at 4 pixels per cell
+    assert!(gs.grid);
+    assert_eq!(gs.grid_lines(), []);
+
+    gs.view.scale = 10.0;
+    let lines = gs.grid_lines();
+
+    // Thicker every 10 cells, starting from the board's edge
+    assert_eq!((lines[0].0, lines[0].1[0]), (2.0, 0.0));
+    assert_eq!((lines[1].0, lines[1].1[0]), (1.0, 1.0));
+    assert!(lines
+        .iter()
+        .all(|(_, coords)| coords.iter().all(|&c| (0.0..=3.0).contains(&c))));
+
+    gs.handle_event(button_event(Key::G));
+
+    assert_eq!(gs.grid_lines(), []);
+}
@@ -0,0 +1,109 @@
+use std::cmp::{max, min};
+
+/// A cell position as (row, column); may lie outside the board
+pub(crate) type Cell = (isize, isize);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Tool {
+    /// Paint cells along the cursor path
+    Draw,
+    /// Paint a straight line from where the button was pressed to where it was released
+    Line,
+    /// Paint the outline of a rectangle with opposite corners at the press and release
+    Rectangle,
+    /// Drag out a rectangular selection to copy, cut or transform
+    Select,
+}
+
+/// A drag in progress with the left mouse button
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Stroke {
+    pub(crate) start: Cell,
+    pub(crate) last: Cell,
+    /// The state painted into every cell
+    pub(crate) state: u8,
+}
+
+impl Tool {
+    /// The cells a finished stroke paints, for tools that paint on release
+    pub(crate) fn shape(self, start: Cell, end: Cell) -> Vec<Cell> {
+        match self {
+            Tool::Draw | Tool::Select => vec![],
+            Tool::Line => line(start, end),
+            Tool::Rectangle => rectangle(start, end),
+        }
+    }
+}
+
+/// Every cell on the line between `from` and `to` (inclusive), by Bresenham's algorithm
+pub(crate) fn line(from: Cell, to: Cell) -> Vec<Cell> {
+    let (d_row, d_col) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+    let (step_row, step_col) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (mut cell, mut err) = (from, d_row + d_col);
+    let mut cells = vec![from];
+
+    while cell != to {
+        let err2 = 2 * err;
+        if err2 >= d_col {
+            err += d_col;
+            cell.0 += step_row;
+        }
+        if err2 <= d_row {
+            err += d_row;
+            cell.1 += step_col;
+        }
+        cells.push(cell);
+    }
+
+    cells
+}
+
+/// The `(left, top, right, bottom)` corners of the rectangle with opposite corners `a` and `b`
+pub(crate) fn bounds(a: Cell, b: Cell) -> (isize, isize, isize, isize) {
+    (min(a.1, b.1), min(a.0, b.0), max(a.1, b.1), max(a.0, b.0))
+}
+
+/// The outline of the rectangle with opposite corners `a` and `b`
+pub(crate) fn rectangle(a: Cell, b: Cell) -> Vec<Cell> {
+    let (left, top, right, bottom) = bounds(a, b);
+
+    let edges = |low: isize, high: isize| {
+        if low == high {
+            vec![low]
+        } else {
+            vec![low, high]
+        }
+    };
+    let (rows, cols) = (edges(top, bottom), edges(left, right));
+
+    (left..=right)
+        .flat_map(|col| rows.iter().map(move |&row| (row, col)))
+        .chain((top + 1..bottom).flat_map(|row| cols.iter().map(move |&col| (row, col))))
+        .collect()
+}
+
+#[test]
+fn test_line() {
+    assert_eq!(line((0, 0), (0, 0)), [(0, 0)]);
+    assert_eq!(line((0, 0), (0, 3)), [(0, 0), (0, 1), (0, 2), (0, 3)]);
+    assert_eq!(line((2, 2), (0, 0)), [(2, 2), (1, 1), (0, 0)]);
+    assert_eq!(line((0, 0), (1, 3)), [(0, 0), (0, 1), (1, 2), (1, 3)]);
+    assert_eq!(line((-1, 0), (1, 0)), [(-1, 0), (0, 0), (1, 0)]);
+}
+
+#[test]
+fn test_bounds() {
+    assert_eq!(bounds((3, 0), (1, 2)), (0, 1, 2, 3));
+    assert_eq!(bounds((-1, 4), (-1, 4)), (4, -1, 4, -1));
+}
+
+#[test]
+fn test_rectangle() {
+    assert_eq!(rectangle((0, 0), (0, 0)), [(0, 0)]);
+    assert_eq!(rectangle((0, 2), (0, 0)), [(0, 0), (0, 1), (0, 2)]);
+
+    let mut cells = rectangle((2, 2), (0, 0));
+    cells.sort();
+    #[rustfmt::skip]
+    assert_eq!(cells, [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)]);
+}
@@ -0,0 +1,91 @@
+use super::{MAX_SCALE, MIN_SCALE};
+
+/// Where the board is drawn in the window
+pub(crate) struct View {
+    /// Pixels per cell side
+    pub(crate) scale: f64,
+    /// Window position of the board's top-left corner
+    pub(crate) offset: [f64; 2],
+    /// The scale the window opened at, which resizing grows the board by whatever the zoom
+    base_scale: f64,
+}
+
+impl View {
+    pub(crate) fn new(scale: f64) -> Self {
+        View {
+            scale,
+            offset: [0.0, 0.0],
+            base_scale: scale,
+        }
+    }
+
+    /// Zoom by `factor`, keeping the point under `center` (in window coordinates) fixed
+    pub(crate) fn zoom_at(&mut self, factor: f64, center: [f64; 2]) {
+        let scale = (self.scale * factor).clamp(MIN_SCALE, MAX_SCALE);
+        let ratio = scale / self.scale;
+
+        self.offset = [
+            center[0] - (center[0] - self.offset[0]) * ratio,
+            center[1] - (center[1] - self.offset[1]) * ratio,
+        ];
+        self.scale = scale;
+    }
+
+    /// Zoom and pan so the cells from `(left, top)` to `(right, bottom)` fill a window of `size`
+    pub(crate) fn zoom_to(
+        &mut self,
+        size: [f64; 2],
+        (left, top, right, bottom): (usize, usize, usize, usize),
+    ) {
+        let (cols, rows) = ((right + 1 - left) as f64, (bottom + 1 - top) as f64);
+
+        self.scale = (size[0] / cols)
+            .min(size[1] / rows)
+            .clamp(MIN_SCALE, MAX_SCALE);
+        self.offset = [
+            (size[0] - cols * self.scale) / 2.0 - left as f64 * self.scale,
+            (size[1] - rows * self.scale) / 2.0 - top as f64 * self.scale,
+        ];
+    }
+
+    /// The `(cols, rows)` a window of `size` holds at the scale it opened at, which the board
+    /// grows to when the window is resized. Zooming out doesn't make the board any bigger.
+    pub(crate) fn cells_to_fill(&self, size: [f64; 2]) -> (usize, usize) {
+        let cells = |pixels: f64| (pixels / self.base_scale).floor() as usize;
+
+        (cells(size[0]), cells(size[1]))
+    }
+}
+
+#[test]
+fn test_zoom_at() {
+    let mut view = View::new(4.0);
+    view.zoom_at(2.0, [100.0, 50.0]);
+
+    assert_eq!(view.scale, 8.0);
+    // The cell that was under the center still is
+    assert_eq!(view.offset, [-100.0, -50.0]);
+
+    view.zoom_at(1e-6, [0.0, 0.0]);
+    assert_eq!(view.scale, MIN_SCALE);
+}
+
+#[test]
+fn test_zoom_to() {
+    let mut view = View::new(4.0);
+    view.zoom_to([800.0, 600.0], (10, 10, 19, 29));
+
+    assert_eq!(view.scale, 30.0);
+    assert_eq!(view.offset, [-50.0, -300.0]);
+}
+
+#[test]
+fn test_resize_while_zoomed_out() {
+    let mut view = View::new(4.0);
+    assert_eq!(view.cells_to_fill([800.0, 600.0]), (200, 150));
+
+    view.zoom_at(1e-6, [400.0, 300.0]);
+    assert_eq!(view.scale, MIN_SCALE);
+    assert_eq!(view.cells_to_fill([800.0, 600.0]), (200, 150));
+    assert_eq!(view.cells_to_fill([1000.0, 600.0]), (250, 150));
+}
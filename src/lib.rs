@@ -1,3 +1,28 @@
+//! Conway's Game of Life and other cellular automata: life-like (`B3/S23`), Larger than Life
+//! and Golly rule-table rules on a finite grid.
+//!
+//! Besides the `rust-life` binary (see [`run`]), the crate can be used as a library:
+//! [`Board`] holds the cells and steps them forward, [`Board::builder`] sets one up from a
+//! size, template and [`Rule`], and boards convert to and from text (with [`str::parse`]
+//! and [`Board`]'s `Display`) and run-length encoding ([`parse_rle`] and [`to_rle`]).
+//!
+//! ```
+//! use rust_life::{parse_rle, to_rle, Board};
+//!
+//! let blinker: Board = "...\n@@@\n...".parse().unwrap();
+//! let next = blinker.next_generation();
+//!
+//! assert_eq!(next.to_string(), ".@.\n.@.\n.@.");
+//! assert_eq!(next.generation(), 1);
+//! assert_eq!(parse_rle(&to_rle(&next)).unwrap(), next);
+//! ```
+//!
+//! [`Board::next_generation`] computes each generation with the board's [`Engine`], chosen
+//! with [`Board::with_engine`] or [`BoardBuilder::engine`] from the built-in
+//! [`EngineKind`]s: [`Serial`], `Rayon` across all cores (needing the `rayon` feature, which
+//! is on by default, and the default when enabled), [`Bitwise`] on bit-packed rows,
+//! [`HashLife`], [`Sparse`] and [`Tiled`]. Every engine gives the same generations.
+
 #![cfg_attr(all(test, feature = "unstable"), feature(test))]
 
 #[cfg(all(test, feature = "unstable"))]
@@ -9,20 +34,65 @@ mod gui;
 #[cfg(all(feature = "test_mainthread", feature = "gui"))]
 pub use gui::test_helper::EXAMPLES;
 
+mod bitwise;
 mod board;
+mod builder;
+#[cfg(feature = "gui")]
+mod color;
+mod engine;
+#[cfg(feature = "gui")]
+mod export;
+mod patterns;
+mod predecessor;
+mod render;
+mod rle;
+mod rule;
+mod rule_table;
+mod sat;
+#[cfg(feature = "gui")]
+mod stats;
+#[cfg(feature = "tui")]
+mod terminal;
+#[cfg(feature = "tui")]
+mod tui;
 
 use std::time::{Duration, Instant};
 
-use board::Board;
+pub use board::{Board, Generations, ParseBoardErr};
+pub use builder::{Alignment, BoardBuilder};
+#[cfg(feature = "rayon")]
+pub use engine::Rayon;
+pub use engine::{Bitwise, Engine, EngineKind, HashLife, Serial, Sparse, Tiled};
+pub use patterns::{find_pattern, parse_template, Pattern, PATTERNS};
+pub use predecessor::{find_predecessor, PredecessorErr};
+pub use rle::{parse_rle, to_rle};
+pub use rule::{LtlRule, Neighborhood, ParseRuleErr, Rule};
+pub use rule_table::RuleTable;
 
+use render::Output;
+
+/// The escape sequence clearing the terminal, printed before each generation of text output
 pub const CLEAR: &str = "\x1b[H\x1b[2J";
 
 mod args;
 
-use args::{parse_args, Alignment, Args};
+use args::{parse_args, Args, Command, PatternsCommand};
 
+/// Run the `rust-life` command line program, with arguments from the environment
 pub fn run() {
     let args = parse_args();
+
+    match args.command {
+        Some(Command::Predecessor { template, rule }) => return predecessor(template, rule),
+        Some(Command::Patterns { command }) => return patterns(command),
+        None => {}
+    }
+
+    #[cfg(feature = "gui")]
+    if let Some(path) = &args.export {
+        return export(&args, path);
+    }
+
     let cli_run_gens = args.generation_limit.or(if args.generations.is_some() {
         Some(0)
     } else {
@@ -30,44 +100,111 @@ pub fn run() {
     });
     let brd = make_board(&args);
 
+    #[cfg(feature = "tui")]
+    if args.tui {
+        let running = args.generations.is_none() || args.generation_limit.is_some();
+        let tui = tui::Tui::new(
+            brd,
+            args.ups,
+            running,
+            args.step_size,
+            args.generation_limit,
+        );
+        if let Err(err) = tui::run(tui) {
+            use clap::{error::ErrorKind, CommandFactory};
+            Args::command().error(ErrorKind::Io, err).exit()
+        }
+        return;
+    }
+
+    let output = Output {
+        render: args.render,
+        color: args.ansi,
+    };
+
     #[cfg(feature = "gui")]
     if args.no_gui {
-        cli(brd, args.ups, cli_run_gens);
+        cli(brd, args.ups, cli_run_gens, output);
     } else {
         gui::run(
             brd,
-            args.scale,
-            args.ups,
-            args.generations.is_none() || args.generation_limit.is_some(),
-            args.generation_limit,
-            args.exit_on_finish,
+            gui::Settings {
+                scale: args.scale,
+                ups: args.ups,
+                running: args.generations.is_none() || args.generation_limit.is_some(),
+                generation_limit: args.generation_limit,
+                exit_on_finish: args.exit_on_finish,
+                history: args.history,
+                step_size: args.step_size,
+                color_mode: args.color_mode,
+                colors: colors(&args),
+                save_path: args.save_path.clone(),
+                grid: args.grid,
+            },
         );
     }
     #[cfg(not(feature = "gui"))]
-    cli(brd, args.ups, cli_run_gens);
+    cli(brd, args.ups, cli_run_gens, output);
 }
 
 fn make_board(args: &Args) -> Board {
-    let mut brd = if let Some(template) = &args.template {
-        let (top, right, bottom, left) = if let Some(padding) = &args.padding {
-            parse_padding(padding)
-        } else {
-            let vertical_padding = (args.rows - template.rows()) as isize;
-            let horizontal_padding = (args.cols - template.cols()) as isize;
+    board_builder(args)
+        .generations(args.generations.unwrap_or(0))
+        .build()
+}
 
-            alignment_padding(args.align, horizontal_padding, vertical_padding)
-        };
+/// The board from the template (or a random one), before advancing any generations
+fn board_builder(args: &Args) -> BoardBuilder {
+    let mut builder = Board::builder()
+        .size(args.rows, args.cols)
+        .engine(args.engine.engine());
 
-        template.pad(top, right, bottom, left)
-    } else {
-        Board::new(args.rows, args.cols).random()
+    builder = match &args.template {
+        Some(template) => builder.template(template.clone()).align(args.align),
+        None => builder.random(),
     };
+    if let Some(padding) = &args.padding {
+        let (top, right, bottom, left) = parse_padding(padding);
+        builder = builder.padding(top, right, bottom, left);
+    }
+    match args.rule.as_ref().or(args.rule_file.as_ref()) {
+        Some(rule) => builder.rule(rule.clone()),
+        None => builder,
+    }
+}
+
+/// The theme's colors with any given on the command line instead
+#[cfg(feature = "gui")]
+fn colors(args: &Args) -> color::Colors {
+    let colors = args.theme.colors();
+
+    color::Colors {
+        live: args.live_color.unwrap_or(colors.live),
+        dead: args.dead_color.unwrap_or(colors.dead),
+        grid: args.grid_color.unwrap_or(colors.grid),
+        ..colors
+    }
+}
+
+/// Save the board as an image, tracking cell history through the initial generations so
+/// the color mode has something to show
+#[cfg(feature = "gui")]
+fn export(args: &Args, path: &std::path::Path) {
+    let mut generations = board_builder(args).build().generations();
+    let mut brd = generations.next().expect("generations never end");
+    let mut stats = stats::CellStats::new(&brd);
 
-    for _ in 0..args.generations.unwrap_or(0) {
-        brd = brd.next_generation();
+    for next in generations.take(args.generations.unwrap_or(0)) {
+        stats.update(&next);
+        brd = next;
     }
 
-    brd
+    let scale = args.scale.round().max(1.0) as u32;
+    if let Err(err) = export::save_image(&brd, &stats, args.color_mode, &colors(args), scale, path)
+    {
+        use clap::{error::ErrorKind, CommandFactory};
+        Args::command().error(ErrorKind::Io, err).exit()
+    }
 }
 
 fn parse_padding(padding: &[isize]) -> (isize, isize, isize, isize) {
@@ -80,45 +217,66 @@ fn parse_padding(padding: &[isize]) -> (isize, isize, isize, isize) {
     }
 }
 
-fn alignment_padding(
-    align: Alignment,
-    horizontal_padding: isize,
-    vertical_padding: isize,
-) -> (isize, isize, isize, isize) {
-    let (top, bottom) = match align {
-        Alignment::TopLeft | Alignment::Top | Alignment::TopRight => (0, vertical_padding),
-        Alignment::Left | Alignment::Center | Alignment::Right => (
-            vertical_padding / 2,
-            vertical_padding / 2 + vertical_padding % 2,
-        ),
-        Alignment::BottomLeft | Alignment::Bottom | Alignment::BottomRight => (vertical_padding, 0),
+fn predecessor(template: Board, rule: Option<Rule>) {
+    let target = match rule {
+        Some(rule) => template.with_rule(rule),
+        None => template,
     };
-    let (left, right) = match align {
-        Alignment::TopLeft | Alignment::Left | Alignment::BottomLeft => (0, horizontal_padding),
-        Alignment::Top | Alignment::Center | Alignment::Bottom => (
-            horizontal_padding / 2,
-            horizontal_padding / 2 + horizontal_padding % 2,
+
+    match find_predecessor(&target) {
+        Ok(Some(parent)) => println!("{parent}"),
+        Ok(None) => println!(
+            "No predecessor exists within the {}x{} box (Garden of Eden)",
+            target.rows(),
+            target.cols()
         ),
-        Alignment::TopRight | Alignment::Right | Alignment::BottomRight => (horizontal_padding, 0),
-    };
+        Err(err) => {
+            use clap::{error::ErrorKind, CommandFactory};
+            Args::command().error(ErrorKind::InvalidValue, err).exit()
+        }
+    }
+}
 
-    (top, right, bottom, left)
+fn patterns(command: PatternsCommand) {
+    match command {
+        PatternsCommand::List => {
+            for pattern in PATTERNS {
+                println!("{:<16}{}", pattern.name, pattern.description);
+            }
+        }
+        PatternsCommand::Show { name } => {
+            let pattern = find_pattern(&name).expect("pattern names are checked by clap");
+            println!("{pattern}");
+        }
+    }
 }
 
-fn cli(mut brd: Board, ups: u64, run_gens: Option<usize>) {
+fn cli(brd: Board, ups: u64, run_gens: Option<usize>, output: Output) {
     if run_gens == Some(0) {
-        println!("{brd}");
+        println!("{}", output.draw(&brd));
     } else {
+        #[cfg(feature = "tui")]
+        if std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+            if let Err(err) = terminal::play(brd, ups, run_gens, output) {
+                use clap::{error::ErrorKind, CommandFactory};
+                Args::command().error(ErrorKind::Io, err).exit()
+            }
+            return;
+        }
+
         let frame_time: Duration = Duration::from_secs_f64(1.0 / ups as f64);
-        let mut frame_start;
+        let mut brd = brd;
 
         while Some(brd.generation()) <= run_gens {
-            frame_start = Instant::now();
-            println!("{CLEAR}{brd}");
-            brd = brd.next_generation();
+            let frame_start = Instant::now();
+            println!("{CLEAR}{}", output.draw(&brd));
             std::thread::sleep(
                 frame_time.saturating_sub(Instant::now().duration_since(frame_start)),
             );
+            if Some(brd.generation()) == run_gens {
+                break;
+            }
+            brd.step_in_place();
         }
     }
 }
@@ -148,19 +306,3 @@ fn test_parse_padding_invalid() {
 fn test_parse_padding_invalid_2() {
     parse_padding(&[1, 2, 3, 4, 5]);
 }
-
-#[test]
-fn test_alignment_padding() {
-    assert_eq!(alignment_padding(Alignment::Top, 2, 2), (0, 1, 2, 1));
-    assert_eq!(alignment_padding(Alignment::TopLeft, 2, 2), (0, 2, 2, 0));
-    assert_eq!(alignment_padding(Alignment::TopRight, 2, 2), (0, 0, 2, 2));
-    assert_eq!(alignment_padding(Alignment::Center, 2, 2), (1, 1, 1, 1));
-    assert_eq!(alignment_padding(Alignment::Left, 2, 2), (1, 2, 1, 0));
-    assert_eq!(alignment_padding(Alignment::Right, 2, 2), (1, 0, 1, 2));
-    assert_eq!(alignment_padding(Alignment::Bottom, 2, 2), (2, 1, 0, 1));
-    assert_eq!(alignment_padding(Alignment::BottomLeft, 2, 2), (2, 2, 0, 0));
-    assert_eq!(
-        alignment_padding(Alignment::BottomRight, 2, 2),
-        (2, 0, 0, 2)
-    );
-}
@@ -0,0 +1,282 @@
+use crate::board::{Board, ParseBoardErr};
+use crate::rle::parse_rle;
+use std::fmt;
+use std::str::FromStr;
+
+/// A well-known Life pattern shipped with the binary
+#[derive(Debug)]
+pub struct Pattern {
+    /// The name given to `--template` and `patterns show`
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Generations before the pattern repeats (possibly displaced), for oscillators,
+    /// spaceships and guns
+    pub period: Option<usize>,
+    /// Distance travelled per period, for spaceships
+    pub speed: Option<&'static str>,
+    pub discoverer: Option<&'static str>,
+    /// When the pattern was discovered
+    pub year: u16,
+    rle: &'static str,
+}
+
+impl Pattern {
+    /// The pattern on the smallest board holding it
+    pub fn board(&self) -> Board {
+        parse_rle(self.rle).expect("built-in patterns are valid RLE")
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}: {}", self.name, self.description)?;
+        if let Some(period) = self.period {
+            writeln!(f, "Period: {period}")?;
+        }
+        if let Some(speed) = self.speed {
+            writeln!(f, "Speed: {speed}")?;
+        }
+        writeln!(
+            f,
+            "Discovered: {}{}",
+            self.discoverer
+                .map_or(String::new(), |who| format!("{who}, ")),
+            self.year
+        )?;
+        write!(f, "\n{}", self.board())
+    }
+}
+
+/// The built-in patterns; the first ten are on the GUI's number keys (`1` to `9`, then `0`)
+pub const PATTERNS: &[Pattern] = &[
+    Pattern {
+        name: "glider",
+        description: "The smallest spaceship, travelling diagonally",
+        period: Some(4),
+        speed: Some("c/4"),
+        discoverer: Some("Richard K. Guy"),
+        year: 1969,
+        rle: "x = 3, y = 3\nbo$2bo$3o!",
+    },
+    Pattern {
+        name: "lwss",
+        description: "Lightweight spaceship, travelling orthogonally",
+        period: Some(4),
+        speed: Some("c/2"),
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 5, y = 4\no2bo$4bo$o3bo$b4o!",
+    },
+    Pattern {
+        name: "mwss",
+        description: "Middleweight spaceship, travelling orthogonally",
+        period: Some(4),
+        speed: Some("c/2"),
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 6, y = 5\n2bo$o3bo$5bo$o4bo$b5o!",
+    },
+    Pattern {
+        name: "r-pentomino",
+        description: "Methuselah that stabilises after 1103 generations",
+        period: None,
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1969,
+        rle: "x = 3, y = 3\nb2o$2o$bo!",
+    },
+    Pattern {
+        name: "acorn",
+        description: "Methuselah that stabilises after 5206 generations",
+        period: None,
+        speed: None,
+        discoverer: Some("Charles Corderman"),
+        year: 1971,
+        rle: "x = 7, y = 3\nbo$3bo$2o2b3o!",
+    },
+    Pattern {
+        name: "diehard",
+        description: "Methuselah that vanishes after 130 generations",
+        period: None,
+        speed: None,
+        discoverer: None,
+        year: 1971,
+        rle: "x = 8, y = 3\n6bo$2o$bo3b3o!",
+    },
+    Pattern {
+        name: "gosper-gun",
+        description: "Gosper glider gun, the first known gun, emitting a glider every period",
+        period: Some(30),
+        speed: None,
+        discoverer: Some("Bill Gosper"),
+        year: 1970,
+        rle: "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+              2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+    },
+    Pattern {
+        name: "pulsar",
+        description: "The most common period 3 oscillator",
+        period: Some(3),
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 13, y = 13\n2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$\
+              o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    },
+    Pattern {
+        name: "pentadecathlon",
+        description: "Period 15 oscillator",
+        period: Some(15),
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 10, y = 3\n2bo4bo$2ob4ob2o$2bo4bo!",
+    },
+    Pattern {
+        name: "blinker",
+        description: "The smallest oscillator",
+        period: Some(2),
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1969,
+        rle: "x = 3, y = 1\n3o!",
+    },
+    Pattern {
+        name: "block",
+        description: "The most common still life",
+        period: Some(1),
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1969,
+        rle: "x = 2, y = 2\n2o$2o!",
+    },
+    Pattern {
+        name: "toad",
+        description: "Period 2 oscillator",
+        period: Some(2),
+        speed: None,
+        discoverer: Some("Simon Norton"),
+        year: 1970,
+        rle: "x = 4, y = 2\nb3o$3o!",
+    },
+    Pattern {
+        name: "beacon",
+        description: "Period 2 oscillator made of two blocks",
+        period: Some(2),
+        speed: None,
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 4, y = 4\n2o$o$3bo$2b2o!",
+    },
+    Pattern {
+        name: "hwss",
+        description: "Heavyweight spaceship, travelling orthogonally",
+        period: Some(4),
+        speed: Some("c/2"),
+        discoverer: Some("John Conway"),
+        year: 1970,
+        rle: "x = 7, y = 5\n2b2o$o4bo$6bo$o5bo$b6o!",
+    },
+];
+
+/// The built-in pattern with the given name
+pub fn find_pattern(name: &str) -> Option<&'static Pattern> {
+    PATTERNS.iter().find(|pattern| pattern.name == name)
+}
+
+/// Parse a template given either as the name of a built-in pattern or as a board string
+pub fn parse_template(template: &str) -> Result<Board, ParseBoardErr> {
+    let is_name = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-';
+
+    match find_pattern(template) {
+        Some(pattern) => Ok(pattern.board()),
+        None if !template.is_empty() && template.chars().all(is_name) => Err(ParseBoardErr(
+            format!("Unknown pattern '{template}' (see `rust-life patterns list`)"),
+        )),
+        None => Board::from_str(template),
+    }
+}
+
+/// The live cells of `brd` cropped to their bounding box
+#[cfg(test)]
+fn content(brd: &Board) -> Board {
+    let (left, top, right, bottom) = brd.bounding_box().unwrap();
+    brd.crop(left, top, right, bottom)
+}
+
+#[test]
+fn test_patterns_parse() {
+    let population = |name| find_pattern(name).unwrap().board().population();
+
+    assert_eq!(population("glider"), 5);
+    assert_eq!(population("lwss"), 9);
+    assert_eq!(population("mwss"), 11);
+    assert_eq!(population("gosper-gun"), 36);
+    assert_eq!(population("pulsar"), 48);
+    assert!(find_pattern("puppies").is_none());
+
+    for pattern in PATTERNS {
+        // Every pattern fills its declared box
+        assert_eq!(
+            content(&pattern.board()),
+            pattern.board(),
+            "{}",
+            pattern.name
+        );
+    }
+}
+
+#[test]
+fn test_patterns_period() {
+    // The gun's period is when it repeats apart from the gliders it has fired
+    for pattern in PATTERNS
+        .iter()
+        .filter(|pattern| pattern.name != "gosper-gun")
+    {
+        if let Some(period) = pattern.period {
+            let start = pattern.board().pad(8, 8, 8, 8);
+            let end = (0..period).fold(start.clone(), |brd, _| brd.next_generation());
+
+            assert_eq!(
+                content(&end).to_string(),
+                content(&start).to_string(),
+                "{}",
+                pattern.name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parse_template() {
+    assert_eq!(
+        parse_template("glider").unwrap().to_string(),
+        ".@.\n..@\n@@@"
+    );
+    assert_eq!(parse_template("@.\n.@").unwrap().to_string(), "@.\n.@");
+    assert_eq!(
+        parse_template("gilder").unwrap_err().to_string(),
+        "Unknown pattern 'gilder' (see `rust-life patterns list`)"
+    );
+    assert_eq!(
+        parse_template("@x").unwrap_err().to_string(),
+        "Unexpected 'x'"
+    );
+}
+
+#[test]
+fn test_pattern_display() {
+    assert_eq!(
+        find_pattern("glider").unwrap().to_string(),
+        "glider: The smallest spaceship, travelling diagonally\n\
+         Period: 4\n\
+         Speed: c/4\n\
+         Discovered: Richard K. Guy, 1969\n\
+         \n\
+         .@.\n..@\n@@@"
+    );
+    assert!(find_pattern("diehard")
+        .unwrap()
+        .to_string()
+        .contains("\nDiscovered: 1971\n"));
+}
@@ -0,0 +1,157 @@
+use crate::board::Board;
+use crate::rule::Rule;
+use crate::sat::{Lit, Solver, Var};
+use std::error::Error;
+use std::fmt;
+
+/// Why a predecessor search couldn't be run, e.g. for a rule it doesn't support
+#[derive(Debug, PartialEq)]
+pub struct PredecessorErr(String);
+
+impl Error for PredecessorErr {}
+
+impl fmt::Display for PredecessorErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Search for a board of the same size which becomes `target` after one generation. Cells
+/// outside the box are dead, so `Ok(None)` means `target` is a Garden of Eden within it.
+pub fn find_predecessor(target: &Board) -> Result<Option<Board>, PredecessorErr> {
+    let (born, survive) = match target.rule() {
+        Rule::LifeLike { born, survive } => (born, survive),
+        rule => {
+            return Err(PredecessorErr(format!(
+                "Predecessor search only supports B/S rules, not '{rule}'"
+            )))
+        }
+    };
+    if target.states().iter().any(|&state| state > 1) {
+        return Err(PredecessorErr("Target has cells in states above 1".into()));
+    }
+
+    let (rows, cols) = (target.rows(), target.cols());
+    let mut solver = Solver::new();
+    let cells: Vec<Var> = (0..rows * cols).map(|_| solver.new_var()).collect();
+
+    for (i, &alive) in target.states().iter().enumerate() {
+        let (x, y) = ((i % cols) as isize, (i / cols) as isize);
+        let center = cells[i];
+        let neighbors: Vec<Var> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+            .filter(|&(nx, ny)| (nx, ny) != (x, y))
+            .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && nx < cols as isize && ny < rows as isize)
+            .map(|(nx, ny)| cells[ny as usize * cols + nx as usize])
+            .collect();
+
+        // Forbid every parent neighborhood whose successor disagrees with the target
+        for assignment in 0u32..1 << (neighbors.len() + 1) {
+            let parent_alive = assignment & 1 == 1;
+            let count = (assignment >> 1).count_ones() as usize;
+            let next = if parent_alive {
+                survive.contains(&count)
+            } else {
+                born.contains(&count)
+            };
+            if next == (alive == 1) {
+                continue;
+            }
+
+            let clause: Vec<Lit> = [center]
+                .iter()
+                .chain(&neighbors)
+                .enumerate()
+                .map(|(bit, var)| var.lit(assignment & (1 << bit) == 0))
+                .collect();
+            if !solver.add_clause(&clause) {
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(solver.solve().map(|model| {
+        target
+            .clear()
+            .with_states(model.into_iter().map(u8::from).collect())
+    }))
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn test_blinker_predecessor() {
+    let target = Board::from_str(".....\n..@..\n..@..\n..@..\n.....").unwrap();
+    let parent = find_predecessor(&target).unwrap().unwrap();
+
+    assert_eq!(parent.next_generation().states(), target.states());
+    assert_eq!(parent.generation(), 0);
+}
+
+#[test]
+fn test_garden_of_eden() {
+    // A lone cell in a 1×1 box has no neighbors to be born or survive from
+    assert_eq!(find_predecessor(&Board::from_str("@").unwrap()), Ok(None));
+    // ...but B0/S is happy to make one from nothing
+    let b0 = Board::from_str("@")
+        .unwrap()
+        .with_rule(Rule::from_str("B0/S").unwrap());
+    assert_eq!(find_predecessor(&b0).unwrap().unwrap().population(), 0);
+}
+
+#[test]
+fn test_predecessor_exhaustive() {
+    // Check the solver against every possible parent of a 3×3 box
+    let empty = Board::new(3, 3);
+    let children: Vec<Vec<u8>> = (0..1 << 9)
+        .map(|parent: u32| {
+            let cells = (0..9).map(|bit| (parent >> bit & 1) as u8).collect();
+            empty
+                .with_states(cells)
+                .serial_next_generation()
+                .states()
+                .to_vec()
+        })
+        .collect();
+
+    for target in 0..1 << 9 {
+        let cells: Vec<u8> = (0..9).map(|bit| (target >> bit & 1) as u8).collect();
+        let target = empty.with_states(cells);
+        let parent = find_predecessor(&target).unwrap();
+
+        assert_eq!(
+            parent.is_some(),
+            children.iter().any(|child| child == target.states())
+        );
+        if let Some(parent) = parent {
+            assert_eq!(parent.serial_next_generation().states(), target.states());
+        }
+    }
+}
+
+#[test]
+fn test_predecessor_random() {
+    let target = Board::new(6, 6).random().next_generation();
+    let parent = find_predecessor(&target).unwrap().unwrap();
+
+    assert_eq!(parent.next_generation().states(), target.states());
+}
+
+#[test]
+fn test_predecessor_errors() {
+    let ltl = Rule::from_str("R2,C0,M0,S2..3,B3..3,NM").unwrap();
+
+    assert_eq!(
+        find_predecessor(&Board::new(2, 2).with_rule(ltl))
+            .unwrap_err()
+            .to_string(),
+        "Predecessor search only supports B/S rules, not 'R2,C0,M0,S2..3,B3..3,NM'"
+    );
+    assert_eq!(
+        find_predecessor(&Board::from_str("B").unwrap())
+            .unwrap_err()
+            .to_string(),
+        "Target has cells in states above 1"
+    );
+}
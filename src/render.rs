@@ -0,0 +1,224 @@
+use std::io::{self, IsTerminal};
+
+use clap::ValueEnum;
+
+use crate::board::{state_char, Board};
+use crate::rule::Rule;
+
+/// How the board is drawn as text
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Render {
+    /// One character per cell, as in board templates
+    #[default]
+    Ascii,
+    /// Unicode half blocks, two cells per character stacked vertically
+    Half,
+    /// Braille patterns, two cells across by four down per character
+    Braille,
+}
+
+impl Render {
+    /// The cells drawn by one character as (rows, columns)
+    fn cell_size(self) -> (usize, usize) {
+        match self {
+            Render::Ascii => (1, 1),
+            Render::Half => (2, 1),
+            Render::Braille => (4, 2),
+        }
+    }
+}
+
+/// The bit of a Braille pattern (from U+2800) for the dot at each (row, column)
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// ANSI colors (as SGR color numbers) for live states the rule doesn't give a color: white,
+/// then yellow, red, blue, green, magenta and cyan
+const STATE_COLORS: [u8; 7] = [7, 3, 1, 4, 2, 5, 6];
+
+/// The SGR parameters setting the foreground (or background) to a live state's color
+fn sgr(rule: &Rule, state: u8, background: bool) -> String {
+    let layer = if background { 4 } else { 3 };
+
+    match rule.color(state) {
+        Some([r, g, b]) => format!("{layer}8;2;{r};{g};{b}"),
+        None => format!(
+            "{layer}{}",
+            STATE_COLORS[(state as usize - 1) % STATE_COLORS.len()]
+        ),
+    }
+}
+
+/// `text` in the given colors, or as is when there are none
+fn paint(text: char, colors: &[String]) -> String {
+    match colors {
+        [] => text.into(),
+        colors => format!("\x1b[{}m{text}\x1b[0m", colors.join(";")),
+    }
+}
+
+/// The smallest number of cells across (and down) each dot can show so the whole board fits
+/// in `width` by `height` characters
+pub(crate) fn fit_scale(brd: &Board, render: Render, width: usize, height: usize) -> usize {
+    let (char_rows, char_cols) = render.cell_size();
+
+    brd.rows()
+        .div_ceil((height * char_rows).max(1))
+        .max(brd.cols().div_ceil((width * char_cols).max(1)))
+        .max(1)
+}
+
+/// The terminal's size in characters as (columns, rows), if output is going to one
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+
+    #[cfg(feature = "tui")]
+    {
+        let (cols, rows) = crossterm::terminal::size().ok()?;
+        Some((cols as usize, rows as usize))
+    }
+    #[cfg(not(feature = "tui"))]
+    {
+        let var = |name| std::env::var(name).ok()?.parse().ok();
+        Some((var("COLUMNS")?, var("LINES")?))
+    }
+}
+
+/// How terminal output draws boards
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Output {
+    pub(crate) render: Render,
+    pub(crate) color: bool,
+}
+
+impl Output {
+    /// How many cells across (and down) each dot shows: enough to fit the terminal if printing
+    /// to one
+    fn scale(self, brd: &Board) -> usize {
+        terminal_size().map_or(1, |(width, height)| {
+            // Leaving a line for the cursor after the board
+            fit_scale(brd, self.render, width, height.saturating_sub(1))
+        })
+    }
+
+    /// The board as text
+    pub(crate) fn draw(self, brd: &Board) -> String {
+        render(brd, self.render, self.scale(brd), self.color)
+    }
+
+    /// The board as rows of characters (with any color escapes)
+    #[cfg(feature = "tui")]
+    pub(crate) fn cells(self, brd: &Board) -> Vec<Vec<String>> {
+        render_cells(brd, self.render, self.scale(brd), self.color)
+    }
+}
+
+/// Draw the board as text, each dot showing a `scale` by `scale` block of cells (live if any
+/// of them are), optionally colored with ANSI escapes
+pub(crate) fn render(brd: &Board, render: Render, scale: usize, color: bool) -> String {
+    render_cells(brd, render, scale, color)
+        .iter()
+        .map(|row| row.concat())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The characters drawing the board, as for `render`, by row
+fn render_cells(brd: &Board, render: Render, scale: usize, color: bool) -> Vec<Vec<String>> {
+    let dot = |row: usize, col: usize| -> u8 {
+        (row * scale..(row + 1) * scale)
+            .flat_map(|row| (col * scale..(col + 1) * scale).map(move |col| brd.cell(row, col)))
+            .max()
+            .unwrap_or(0)
+    };
+    let (char_rows, char_cols) = render.cell_size();
+    let (rows, cols) = (
+        brd.rows().div_ceil(scale * char_rows),
+        brd.cols().div_ceil(scale * char_cols),
+    );
+    let rule = brd.rule();
+    let fg = |state: u8| match (color, state) {
+        (false, _) | (_, 0) => vec![],
+        (true, state) => vec![sgr(rule, state, false)],
+    };
+
+    let character = |row: usize, col: usize| match render {
+        Render::Ascii => {
+            let state = dot(row, col);
+            paint(state_char(state), &fg(state))
+        }
+        Render::Half => match (dot(2 * row, col), dot(2 * row + 1, col)) {
+            (0, 0) => " ".into(),
+            (top, 0) => paint('▀', &fg(top)),
+            (0, bottom) => paint('▄', &fg(bottom)),
+            (top, bottom) if top == bottom || !color => paint('█', &fg(top)),
+            (top, bottom) => paint('▀', &[sgr(rule, top, false), sgr(rule, bottom, true)]),
+        },
+        Render::Braille => {
+            let dots = (0..4)
+                .flat_map(|y| (0..2).map(move |x| (y, x)))
+                .filter_map(|(y, x)| {
+                    let state = dot(4 * row + y, 2 * col + x);
+                    (state != 0).then_some((BRAILLE_DOTS[y][x], state))
+                });
+            let (bits, state) = dots.fold((0, 0), |(bits, first), (bit, state)| {
+                (bits | bit, if first == 0 { state } else { first })
+            });
+            let pattern = char::from_u32(0x2800 + bits).expect("Braille patterns are valid chars");
+            paint(pattern, &fg(state))
+        }
+    };
+
+    (0..rows)
+        .map(|row| (0..cols).map(|col| character(row, col)).collect())
+        .collect()
+}
+
+#[test]
+fn test_render() {
+    use std::str::FromStr;
+
+    let glider = Board::from_str(".@.\n..@\n@@@").unwrap();
+
+    assert_eq!(render(&glider, Render::Ascii, 1, false), glider.to_string());
+    assert_eq!(render(&glider, Render::Half, 1, false), " ▀▄\n▀▀▀");
+    assert_eq!(render(&glider, Render::Braille, 1, false), "⠬⠆");
+    assert_eq!(render(&Board::new(2, 2), Render::Braille, 1, false), "⠀");
+
+    // Each dot covers a 2x2 block, live if any cell in it is
+    assert_eq!(render(&glider, Render::Ascii, 2, false), "@@\n@@");
+    assert_eq!(render(&glider, Render::Half, 2, false), "██");
+}
+
+#[test]
+fn test_render_color() {
+    use std::str::FromStr;
+
+    let brd = Board::from_str("@.\nAB").unwrap();
+
+    assert_eq!(
+        render(&brd, Render::Ascii, 1, true),
+        "\x1b[37m@\x1b[0m.\n\x1b[37m@\x1b[0m\x1b[33mB\x1b[0m"
+    );
+    assert_eq!(
+        render(&brd, Render::Half, 1, true),
+        "\x1b[37m█\x1b[0m\x1b[33m▄\x1b[0m"
+    );
+    assert_eq!(
+        render(&Board::from_str("B\nA").unwrap(), Render::Half, 1, true),
+        "\x1b[33;47m▀\x1b[0m"
+    );
+}
+
+#[test]
+fn test_fit_scale() {
+    let brd = Board::new(400, 640);
+
+    assert_eq!(fit_scale(&brd, Render::Ascii, 640, 400), 1);
+    assert_eq!(fit_scale(&brd, Render::Ascii, 80, 24), 17);
+    assert_eq!(fit_scale(&brd, Render::Half, 80, 24), 9);
+    assert_eq!(fit_scale(&brd, Render::Braille, 80, 24), 5);
+    assert_eq!(fit_scale(&brd, Render::Braille, 320, 100), 1);
+    assert_eq!(fit_scale(&brd, Render::Braille, 0, 0), 640);
+}
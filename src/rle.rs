@@ -0,0 +1,294 @@
+use crate::board::{Board, ParseBoardErr};
+use crate::rule::Rule;
+use std::str::FromStr;
+
+/// Longest line written in the RLE body
+const MAX_LINE: usize = 70;
+
+/// The RLE tag for a cell state: `b`/`o` for two-state patterns, otherwise Golly's
+/// multi-state letters (`.`, `A`..`X`, then `pA`..`yO`)
+fn tag(state: u8, multistate: bool) -> String {
+    match (state, multistate) {
+        (0, false) => "b".into(),
+        (_, false) => "o".into(),
+        (0, true) => ".".into(),
+        (n, true) if n <= 24 => ((b'A' + n - 1) as char).into(),
+        (n, true) => {
+            let (prefix, letter) = ((n - 1) / 24, (n - 1) % 24);
+            format!("{}{}", (b'o' + prefix) as char, (b'A' + letter) as char)
+        }
+    }
+}
+
+/// Encode a board in run-length encoded format, with a header giving its size and rule. Boards
+/// past generation 0 get a Golly `#CXRLE` line recording the generation.
+pub fn to_rle(brd: &Board) -> String {
+    let multistate = brd.states().iter().any(|&state| state > 1);
+    let mut items: Vec<(usize, String)> = vec![];
+    fn push(items: &mut Vec<(usize, String)>, count: usize, tag: String) {
+        match items.last_mut() {
+            Some((run, last)) if *last == tag => *run += count,
+            _ => items.push((count, tag)),
+        }
+    }
+
+    for (i, row) in brd.states().chunks(brd.cols().max(1)).enumerate() {
+        if i > 0 {
+            push(&mut items, 1, "$".into());
+        }
+        // Trailing dead cells in a row are implied
+        let len = row
+            .iter()
+            .rposition(|&state| state != 0)
+            .map_or(0, |n| n + 1);
+        for &state in &row[..len] {
+            push(&mut items, 1, tag(state, multistate));
+        }
+    }
+    // ...and so are trailing empty rows
+    while matches!(items.last(), Some((_, tag)) if tag == "$") {
+        items.pop();
+    }
+
+    let mut body = String::new();
+    let mut line_len = 0;
+    for item in items
+        .into_iter()
+        .map(|(count, tag)| match count {
+            1 => tag,
+            n => format!("{n}{tag}"),
+        })
+        .chain(["!".to_string()])
+    {
+        if line_len + item.len() > MAX_LINE {
+            body.push('\n');
+            line_len = 0;
+        }
+        line_len += item.len();
+        body.push_str(&item);
+    }
+
+    let extended = match brd.generation() {
+        0 => String::new(),
+        generation => format!("#CXRLE Gen={generation}\n"),
+    };
+
+    format!(
+        "{extended}x = {}, y = {}, rule = {}\n{body}\n",
+        brd.cols(),
+        brd.rows(),
+        brd.rule()
+    )
+}
+
+fn parse_header(line: &str, current: &Rule) -> Result<(usize, usize, Option<Rule>), ParseBoardErr> {
+    let (mut cols, mut rows) = (None, None);
+    // The rule goes last and runs to the end of the line, since Larger than Life rules contain
+    // commas of their own
+    let (sizes, rule) = match line.find("rule") {
+        Some(start) => (&line[..start], Some(&line[start..])),
+        None => (line, None),
+    };
+
+    for field in sizes.trim_end().trim_end_matches(',').split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| ParseBoardErr(format!("Bad RLE header field '{field}'")))?;
+        let value = value.trim();
+        let size = || {
+            value
+                .parse::<usize>()
+                .map_err(|_| ParseBoardErr(format!("Bad RLE size '{value}'")))
+        };
+
+        match key.trim() {
+            "x" => cols = Some(size()?),
+            "y" => rows = Some(size()?),
+            _ => {}
+        }
+    }
+
+    let rule = rule
+        .map(|field| {
+            let value = field["rule".len()..]
+                .trim_start()
+                .strip_prefix('=')
+                .ok_or_else(|| ParseBoardErr(format!("Bad RLE header field '{field}'")))?
+                .trim();
+            // A named rule table can't be rebuilt from its name, so only the current one is known
+            if value == current.to_string() {
+                return Ok(current.clone());
+            }
+            Rule::from_str(value).map_err(|_| ParseBoardErr(format!("Unknown RLE rule '{value}'")))
+        })
+        .transpose()?;
+
+    match (cols, rows) {
+        (Some(cols), Some(rows)) => Ok((cols, rows, rule)),
+        _ => Err(ParseBoardErr("RLE header needs x and y".into())),
+    }
+}
+
+/// The generation from a Golly `#CXRLE` line such as `#CXRLE Pos=0,0 Gen=12`
+fn parse_generation(string: &str) -> Result<usize, ParseBoardErr> {
+    let generation = string
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#CXRLE"))
+        .flat_map(str::split_whitespace)
+        .find_map(|field| field.strip_prefix("Gen="));
+
+    generation.map_or(Ok(0), |generation| {
+        generation
+            .parse()
+            .map_err(|_| ParseBoardErr(format!("Bad RLE generation '{generation}'")))
+    })
+}
+
+/// Decode a run-length encoded pattern
+pub fn parse_rle(string: &str) -> Result<Board, ParseBoardErr> {
+    parse_rle_with(string, &Rule::default())
+}
+
+/// Decode a run-length encoded pattern, resolving a rule named like `current` (e.g. a rule table
+/// loaded with `--rule-file`) to it
+pub(crate) fn parse_rle_with(string: &str, current: &Rule) -> Result<Board, ParseBoardErr> {
+    let generation = parse_generation(string)?;
+    let mut lines = string
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let header = lines
+        .next()
+        .ok_or_else(|| ParseBoardErr("Empty RLE".into()))?;
+    let (cols, rows, rule) = parse_header(header, current)?;
+
+    let mut cells = vec![0u8; rows * cols];
+    let (mut row, mut col) = (0, 0);
+    let (mut count, mut prefix) = (None::<usize>, None::<u16>);
+
+    for c in lines.flat_map(str::chars).filter(|c| !c.is_whitespace()) {
+        if let Some(digit) = c.to_digit(10) {
+            count = Some(count.unwrap_or(0) * 10 + digit as usize);
+            continue;
+        }
+        let run = count.take().unwrap_or(1);
+        let state = match c {
+            '!' => break,
+            '$' => {
+                row += run;
+                col = 0;
+                continue;
+            }
+            'b' | '.' => 0,
+            'o' => 1,
+            'p'..='y' if prefix.is_none() => {
+                prefix = Some((c as u8 - b'o') as u16);
+                count = Some(run);
+                continue;
+            }
+            'A'..='X' => {
+                let state = prefix.take().unwrap_or(0) * 24 + (c as u8 - b'A') as u16 + 1;
+                u8::try_from(state)
+                    .map_err(|_| ParseBoardErr(format!("State {state} out of range in RLE")))?
+            }
+            c => return Err(ParseBoardErr(format!("Unexpected '{c}' in RLE"))),
+        };
+
+        for _ in 0..run {
+            if row >= rows || col >= cols {
+                return Err(ParseBoardErr("RLE pattern exceeds its size".into()));
+            }
+            cells[row * cols + col] = state;
+            col += 1;
+        }
+    }
+
+    let brd = Board::new(rows, cols)
+        .with_states(cells)
+        .with_generation(generation);
+    Ok(match rule {
+        Some(rule) => brd.with_rule(rule),
+        None => brd,
+    })
+}
+
+#[test]
+fn test_to_rle() {
+    let glider = Board::from_str(".@.\n..@\n@@@").unwrap();
+
+    assert_eq!(to_rle(&glider), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    assert_eq!(
+        to_rle(&Board::from_str("...\n@..\n...").unwrap()),
+        "x = 3, y = 3, rule = B3/S23\n$o!\n"
+    );
+    assert_eq!(
+        to_rle(&Board::from_str(".AB\nCX.").unwrap()),
+        "x = 3, y = 2, rule = B3/S23\n.AB$CX!\n"
+    );
+    assert_eq!(tag(25, true), "pA");
+    assert_eq!(tag(255, true), "yO");
+
+    assert_eq!(
+        to_rle(&glider.next_generation()),
+        "#CXRLE Gen=1\nx = 3, y = 3, rule = B3/S23\n$obo$b2o!\n"
+    );
+
+    let wide = Board::new(1, 200).with_states([1, 0].repeat(100));
+    let rle = to_rle(&wide);
+    assert!(rle.lines().all(|line| line.len() <= MAX_LINE));
+    assert_eq!(parse_rle(&rle).unwrap(), wide);
+}
+
+#[test]
+fn test_parse_rle() {
+    use crate::rule_table::{RuleTable, WIREWORLD};
+
+    let glider = parse_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+
+    assert_eq!(glider.to_string(), ".@.\n..@\n@@@");
+    assert_eq!(glider.rule(), &Rule::default());
+
+    let highlife = parse_rle("x = 4, y = 3, rule = B36/S23\n2o$\n\n2$!").unwrap();
+    assert_eq!(highlife.to_string(), "@@..\n....\n....");
+    assert_eq!(highlife.rule(), &Rule::from_str("B36/S23").unwrap());
+
+    let bosco = Rule::from_str("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    let ltl = parse_rle("x = 2, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\n2o!").unwrap();
+    assert_eq!(ltl.rule(), &bosco);
+    assert_eq!(parse_rle(&to_rle(&ltl)).unwrap(), ltl);
+
+    let wireworld = Rule::Table(RuleTable::parse(WIREWORLD).unwrap());
+    let multistate = parse_rle_with("x = 3, y = 1, rule = WireWorld\n.pAC!", &wireworld).unwrap();
+    assert_eq!(multistate.states(), &[0, 25, 3]);
+    assert_eq!(multistate.rule(), &wireworld);
+
+    assert_eq!(parse_rle("x = 12, y = 1\n12o!").unwrap().population(), 12);
+
+    let resumed = parse_rle("#CXRLE Pos=-1,-1 Gen=42\nx = 1, y = 1\no!").unwrap();
+    assert_eq!(resumed.generation(), 42);
+    assert_eq!(glider.generation(), 0);
+}
+
+#[test]
+fn test_parse_rle_errors() {
+    let err = |rle: &str| parse_rle(rle).unwrap_err().to_string();
+
+    assert_eq!(err(""), "Empty RLE");
+    assert_eq!(err("x = 3\nooo!"), "RLE header needs x and y");
+    assert_eq!(err("x = 2, y = 1\nooo!"), "RLE pattern exceeds its size");
+    assert_eq!(err("x = 2, y = 1\noz!"), "Unexpected 'z' in RLE");
+    assert_eq!(err("x = a, y = 1\n!"), "Bad RLE size 'a'");
+    assert_eq!(err("x = 1, y = 1\nyX!"), "State 264 out of range in RLE");
+    assert_eq!(
+        err("x = 1, y = 1, rule = WireWorld\no!"),
+        "Unknown RLE rule 'WireWorld'"
+    );
+    assert_eq!(
+        err("x = 1, y = 1, rule B3/S23\no!"),
+        "Bad RLE header field 'rule B3/S23'"
+    );
+    assert_eq!(
+        err("#CXRLE Gen=soon\nx = 1, y = 1\no!"),
+        "Bad RLE generation 'soon'"
+    );
+}
@@ -0,0 +1,389 @@
+use crate::rule_table::RuleTable;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How cells change from one generation to the next. Parses from and displays as B/S or
+/// Larger than Life notation.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Rule {
+    /// Outer-totalistic rules over the 3×3 Moore neighborhood, e.g. `B3/S23`
+    LifeLike {
+        /// Neighbor counts at which dead cells come alive
+        born: Vec<usize>,
+        /// Neighbor counts at which live cells stay alive
+        survive: Vec<usize>,
+    },
+    /// Larger than Life rules, e.g. `R5,C0,M1,S34..58,B34..45,NM`
+    LargerThanLife(LtlRule),
+    /// Multi-state rules loaded from a Golly `.rule` file
+    Table(RuleTable),
+}
+
+/// Which cells within a Larger than Life rule's range count as neighbors
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Neighborhood {
+    /// The whole square
+    Moore,
+    /// The diamond of cells within the range in Manhattan distance
+    VonNeumann,
+}
+
+/// A Larger than Life rule, counting neighbors over a larger range
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct LtlRule {
+    pub(crate) range: usize,
+    pub(crate) middle: bool,
+    pub(crate) survive: RangeInclusive<usize>,
+    pub(crate) born: RangeInclusive<usize>,
+    pub(crate) neighborhood: Neighborhood,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::LifeLike {
+            born: vec![3],
+            survive: vec![2, 3],
+        }
+    }
+}
+
+impl Rule {
+    /// Load a Golly-style `.rule` file
+    pub fn load(path: &str) -> Result<Rule, ParseRuleErr> {
+        RuleTable::load(path).map(Rule::Table)
+    }
+
+    /// Number of distinct cell states
+    pub fn states(&self) -> u8 {
+        match self {
+            Rule::Table(table) => table.states(),
+            _ => 2,
+        }
+    }
+
+    /// Display color for a state, if the rule defines one
+    pub fn color(&self, state: u8) -> Option<[u8; 3]> {
+        match self {
+            Rule::Table(table) => table.color(state),
+            _ => None,
+        }
+    }
+
+    /// How far away (in rows or columns) a cell's neighborhood reaches
+    pub(crate) fn range(&self) -> usize {
+        match self {
+            Rule::LargerThanLife(rule) => rule.range,
+            Rule::LifeLike { .. } | Rule::Table(_) => 1,
+        }
+    }
+}
+
+impl LtlRule {
+    pub(crate) fn successor(&self, alive: bool, count: usize) -> bool {
+        if alive {
+            self.survive.contains(&count)
+        } else {
+            self.born.contains(&count)
+        }
+    }
+}
+
+/// Summed-area table of live cells, used to count large neighborhoods in O(1) per row span
+pub(crate) struct PrefixSums {
+    sums: Vec<usize>,
+    rows: usize,
+    cols: usize,
+}
+
+impl PrefixSums {
+    pub(crate) fn new(cells: impl Iterator<Item = bool>, rows: usize, cols: usize) -> Self {
+        let stride = cols + 1;
+        let mut sums = vec![0; (rows + 1) * stride];
+
+        for (i, cell) in cells.enumerate() {
+            let (x, y) = (i % cols + 1, i / cols + 1);
+            sums[y * stride + x] =
+                cell as usize + sums[(y - 1) * stride + x] + sums[y * stride + x - 1]
+                    - sums[(y - 1) * stride + x - 1];
+        }
+
+        PrefixSums { sums, rows, cols }
+    }
+
+    /// Live cells in the rectangle spanning `x0..=x1`, `y0..=y1`, clipped to the board
+    fn rect(&self, x0: isize, y0: isize, x1: isize, y1: isize) -> usize {
+        let clip = |v: isize, max: usize| v.clamp(0, max as isize) as usize;
+        let (x0, x1) = (clip(x0, self.cols), clip(x1 + 1, self.cols));
+        let (y0, y1) = (clip(y0, self.rows), clip(y1 + 1, self.rows));
+        if x0 >= x1 || y0 >= y1 {
+            return 0;
+        }
+        let stride = self.cols + 1;
+
+        self.sums[y1 * stride + x1] + self.sums[y0 * stride + x0]
+            - self.sums[y0 * stride + x1]
+            - self.sums[y1 * stride + x0]
+    }
+
+    pub(crate) fn count(&self, rule: &LtlRule, x: usize, y: usize, alive: bool) -> usize {
+        let (x, y, r) = (x as isize, y as isize, rule.range as isize);
+        let total = match rule.neighborhood {
+            Neighborhood::Moore => self.rect(x - r, y - r, x + r, y + r),
+            Neighborhood::VonNeumann => (-r..=r)
+                .map(|dy| {
+                    let w = r - dy.abs();
+                    self.rect(x - w, y + dy, x + w, y + dy)
+                })
+                .sum(),
+        };
+
+        if rule.middle {
+            total
+        } else {
+            total - alive as usize
+        }
+    }
+}
+
+/// Why a rule couldn't be parsed or loaded
+#[derive(Debug, PartialEq)]
+pub struct ParseRuleErr(pub(crate) String);
+
+impl Error for ParseRuleErr {}
+
+impl fmt::Display for ParseRuleErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn parse_digits(digits: &str) -> Result<Vec<usize>, ParseRuleErr> {
+    digits
+        .chars()
+        .map(|c| match c.to_digit(9) {
+            Some(n) => Ok(n as usize),
+            None => Err(ParseRuleErr(format!("Unexpected '{c}' in neighbor counts"))),
+        })
+        .collect()
+}
+
+fn parse_life_like(string: &str) -> Result<Rule, ParseRuleErr> {
+    let (first, second) = string
+        .split_once('/')
+        .ok_or_else(|| ParseRuleErr(format!("Unrecognized rule '{string}'")))?;
+    let strip = |part: &str, prefix: char| {
+        part.strip_prefix(prefix)
+            .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+            .map(str::to_owned)
+    };
+
+    let lettered = |part: &str| part.starts_with(|c: char| c.is_ascii_alphabetic());
+
+    let (born, survive) = match (strip(first, 'B'), strip(second, 'S')) {
+        (Some(born), Some(survive)) => (born, survive),
+        _ => match (strip(first, 'S'), strip(second, 'B')) {
+            // Survival first, e.g. `S23/B3`
+            (Some(survive), Some(born)) => (born, survive),
+            _ if lettered(first) || lettered(second) => {
+                return Err(ParseRuleErr(format!(
+                    "Unrecognized rule '{string}', expected B.../S... (e.g. B3/S23)"
+                )))
+            }
+            // Legacy S/B notation, e.g. `23/3`
+            _ => (second.to_owned(), first.to_owned()),
+        },
+    };
+
+    Ok(Rule::LifeLike {
+        born: parse_digits(&born)?,
+        survive: parse_digits(&survive)?,
+    })
+}
+
+fn parse_ltl_range(value: &str) -> Result<RangeInclusive<usize>, ParseRuleErr> {
+    let err = || ParseRuleErr(format!("Bad range '{value}'"));
+    let (min, max) = value.split_once("..").ok_or_else(err)?;
+
+    Ok(min.parse().map_err(|_| err())?..=max.parse().map_err(|_| err())?)
+}
+
+fn parse_ltl(string: &str) -> Result<Rule, ParseRuleErr> {
+    let (mut range, mut middle, mut neighborhood) = (None, None, Neighborhood::Moore);
+    let (mut survive, mut born) = (None, None);
+
+    for field in string.split(',') {
+        let err = || ParseRuleErr(format!("Bad field '{field}'"));
+        let mut chars = field.chars();
+        let key = chars.next().ok_or_else(err)?.to_ascii_uppercase();
+        let value = chars.as_str();
+
+        match key {
+            'R' => range = Some(value.parse::<usize>().map_err(|_| err())?),
+            'C' => match value.parse::<usize>().map_err(|_| err())? {
+                0..=2 => {}
+                _ => return Err(ParseRuleErr("Only 2-state rules are supported".into())),
+            },
+            'M' => {
+                middle = Some(match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(err()),
+                })
+            }
+            'S' => survive = Some(parse_ltl_range(value)?),
+            'B' => born = Some(parse_ltl_range(value)?),
+            'N' => {
+                neighborhood = match value {
+                    "M" | "m" => Neighborhood::Moore,
+                    "N" | "n" => Neighborhood::VonNeumann,
+                    _ => return Err(err()),
+                }
+            }
+            _ => return Err(err()),
+        }
+    }
+
+    let missing = |name: &str| ParseRuleErr(format!("Missing '{name}' field"));
+
+    Ok(Rule::LargerThanLife(LtlRule {
+        range: range.filter(|&r| r > 0).ok_or_else(|| missing("R"))?,
+        middle: middle.unwrap_or(false),
+        survive: survive.ok_or_else(|| missing("S"))?,
+        born: born.ok_or_else(|| missing("B"))?,
+        neighborhood,
+    }))
+}
+
+impl FromStr for Rule {
+    type Err = ParseRuleErr;
+
+    fn from_str(string: &str) -> Result<Rule, ParseRuleErr> {
+        let string = string.trim();
+
+        if string.contains(',') {
+            parse_ltl(string)
+        } else {
+            parse_life_like(string)
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &[usize]| -> String { counts.iter().map(usize::to_string).collect() };
+
+        match self {
+            Rule::LifeLike { born, survive } => {
+                write!(f, "B{}/S{}", digits(born), digits(survive))
+            }
+            Rule::LargerThanLife(rule) => write!(
+                f,
+                "R{},C0,M{},S{}..{},B{}..{},N{}",
+                rule.range,
+                rule.middle as u8,
+                rule.survive.start(),
+                rule.survive.end(),
+                rule.born.start(),
+                rule.born.end(),
+                match rule.neighborhood {
+                    Neighborhood::Moore => 'M',
+                    Neighborhood::VonNeumann => 'N',
+                }
+            ),
+            Rule::Table(table) => write!(f, "{}", table.name()),
+        }
+    }
+}
+
+#[test]
+fn test_parse_life_like() {
+    assert_eq!(Rule::from_str("B3/S23"), Ok(Rule::default()));
+    assert_eq!(Rule::from_str("b3/s23"), Ok(Rule::default()));
+    assert_eq!(Rule::from_str("23/3"), Ok(Rule::default()));
+    assert_eq!(Rule::from_str("S23/B3"), Ok(Rule::default()));
+    assert_eq!(Rule::from_str("s23/b36"), Rule::from_str("B36/S23"));
+    assert_eq!(
+        Rule::from_str("B3/B23").unwrap_err().to_string(),
+        "Unrecognized rule 'B3/B23', expected B.../S... (e.g. B3/S23)"
+    );
+    assert_eq!(
+        Rule::from_str("B36/S23").unwrap().to_string(),
+        "B36/S23".to_string()
+    );
+    assert_eq!(
+        Rule::from_str("B3/S2x").unwrap_err().to_string(),
+        "Unexpected 'x' in neighbor counts"
+    );
+    assert!(Rule::from_str("life").is_err());
+}
+
+#[test]
+fn test_parse_ltl() {
+    let bosco = "R5,C0,M1,S34..58,B34..45,NM";
+
+    assert_eq!(
+        Rule::from_str(bosco),
+        Ok(Rule::LargerThanLife(LtlRule {
+            range: 5,
+            middle: true,
+            survive: 34..=58,
+            born: 34..=45,
+            neighborhood: Neighborhood::Moore,
+        }))
+    );
+    assert_eq!(Rule::from_str(bosco).unwrap().to_string(), bosco);
+    assert_eq!(
+        Rule::from_str("R1,C0,M0,S2..3,B3..3,NN")
+            .unwrap()
+            .to_string(),
+        "R1,C0,M0,S2..3,B3..3,NN"
+    );
+    assert_eq!(
+        Rule::from_str("R5,C3,M1,S34..58,B34..45,NM").unwrap_err(),
+        ParseRuleErr("Only 2-state rules are supported".into())
+    );
+    assert_eq!(
+        Rule::from_str("R5,M1,S34..58,NM").unwrap_err(),
+        ParseRuleErr("Missing 'B' field".into())
+    );
+    assert_eq!(
+        Rule::from_str("R5,C0,M1,S34-58,B34..45,NM").unwrap_err(),
+        ParseRuleErr("Bad range '34-58'".into())
+    );
+    for middle in ["M2", "Mx", "M"] {
+        assert_eq!(
+            Rule::from_str(&format!("R5,C0,{middle},S34..58,B34..45,NM")).unwrap_err(),
+            ParseRuleErr(format!("Bad field '{middle}'"))
+        );
+    }
+}
+
+#[test]
+fn test_prefix_sums_count() {
+    let cells = [true; 25];
+    let sums = PrefixSums::new(cells.into_iter(), 5, 5);
+    let rule = |middle, neighborhood| LtlRule {
+        range: 2,
+        middle,
+        survive: 0..=0,
+        born: 0..=0,
+        neighborhood,
+    };
+
+    assert_eq!(sums.count(&rule(true, Neighborhood::Moore), 2, 2, true), 25);
+    assert_eq!(
+        sums.count(&rule(false, Neighborhood::Moore), 2, 2, true),
+        24
+    );
+    assert_eq!(sums.count(&rule(true, Neighborhood::Moore), 0, 0, true), 9);
+    assert_eq!(
+        sums.count(&rule(true, Neighborhood::VonNeumann), 2, 2, true),
+        13
+    );
+    assert_eq!(
+        sums.count(&rule(false, Neighborhood::VonNeumann), 0, 0, true),
+        5
+    );
+}
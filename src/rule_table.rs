@@ -0,0 +1,668 @@
+use crate::rule::ParseRuleErr;
+use std::collections::HashMap;
+use std::fs;
+
+/// Tables with at most this many neighborhood configurations get a lookup entry for every
+/// one of them; bigger ones (e.g. Moore tables with 5 or more states) only store the
+/// configurations their transitions give
+const MAX_DENSE_SIZE: usize = 1 << 20;
+/// Most neighborhood configurations a single transition can match
+const MAX_TRANSITION_SIZE: usize = 1 << 24;
+
+/// A cell and its neighbors, center first, with trailing zeros past the neighborhood's size
+type Cells = [u8; 9];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum TableNeighborhood {
+    /// Center, N, NE, E, SE, S, SW, W, NW
+    Moore,
+    /// Center, N, E, S, W
+    VonNeumann,
+}
+
+impl TableNeighborhood {
+    fn size(self) -> usize {
+        match self {
+            TableNeighborhood::Moore => 9,
+            TableNeighborhood::VonNeumann => 5,
+        }
+    }
+
+    /// Offsets of each cell in the neighborhood, in Golly's order
+    pub(crate) fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            #[rustfmt::skip]
+            TableNeighborhood::Moore => &[
+                (0, 0), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1),
+            ],
+            TableNeighborhood::VonNeumann => &[(0, 0), (0, -1), (1, 0), (0, 1), (-1, 0)],
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Symmetry {
+    /// Images of the neighbor ring (excluding the center) under each group element
+    Group(Vec<Vec<usize>>),
+    /// Any permutation of the neighbors, i.e. only the counts of each state matter
+    Permute,
+}
+
+impl Symmetry {
+    fn parse(name: &str, neighborhood: TableNeighborhood) -> Result<Self, ParseRuleErr> {
+        let ring = neighborhood.size() - 1;
+        let rotate = |step: usize, by: usize| -> Vec<usize> {
+            (0..ring).map(|i| (i + step * by) % ring).collect()
+        };
+        // Mirror left and right: N and S stay put
+        let reflect: Vec<usize> = (0..ring).map(|i| (ring - i) % ring).collect();
+        let rotations = |step: usize| -> Vec<Vec<usize>> {
+            (0..ring / step).map(|by| rotate(step, by)).collect()
+        };
+        let with_reflections = |group: Vec<Vec<usize>>| -> Vec<Vec<usize>> {
+            let reflected: Vec<Vec<usize>> = group
+                .iter()
+                .map(|perm| reflect.iter().map(|&i| perm[i]).collect())
+                .collect();
+            group.into_iter().chain(reflected).collect()
+        };
+        let quarter_turn = ring / 4;
+
+        let group = match (name, neighborhood) {
+            ("none", _) => vec![rotate(1, 0)],
+            ("rotate4", _) => rotations(quarter_turn),
+            ("rotate8", TableNeighborhood::Moore) => rotations(1),
+            ("reflect_horizontal", _) => vec![rotate(1, 0), reflect.clone()],
+            ("rotate4reflect", _) => with_reflections(rotations(quarter_turn)),
+            ("rotate8reflect", TableNeighborhood::Moore) => with_reflections(rotations(1)),
+            ("permute", _) => return Ok(Symmetry::Permute),
+            _ => {
+                return Err(ParseRuleErr(format!(
+                    "Unsupported symmetries '{name}' for {neighborhood:?} neighborhood"
+                )))
+            }
+        };
+
+        Ok(Symmetry::Group(group))
+    }
+
+    /// Rewrite the neighbors in `cells` (center first) to the smallest equivalent configuration
+    fn canonicalize(&self, cells: &mut [u8]) {
+        let neighbors = &mut cells[1..];
+
+        match self {
+            Symmetry::Permute => neighbors.sort_unstable(),
+            Symmetry::Group(group) => {
+                let ring = neighbors.len();
+                let mut smallest = [u8::MAX; 8];
+                for perm in group {
+                    let mut image = [0; 8];
+                    for (cell, &i) in image.iter_mut().zip(perm) {
+                        *cell = neighbors[i];
+                    }
+                    smallest = smallest.min(image);
+                }
+                neighbors.copy_from_slice(&smallest[..ring]);
+            }
+        }
+    }
+}
+
+/// The next state of each neighborhood configuration
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Lookup {
+    /// By the index of every configuration
+    Dense(Vec<u8>),
+    /// By canonical configuration, for just those the transitions give; the rest stay unchanged
+    Sparse(HashMap<Cells, u8>),
+}
+
+/// A Golly-style `@TABLE` rule compiled into a lookup table
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RuleTable {
+    name: String,
+    states: u8,
+    neighborhood: TableNeighborhood,
+    symmetry: Symmetry,
+    lookup: Lookup,
+    colors: Vec<Option<[u8; 3]>>,
+}
+
+#[derive(Clone, Debug)]
+enum Entry {
+    State(u8),
+    Var(String),
+    Set(Vec<u8>),
+}
+
+fn parse_state(token: &str, states: usize) -> Result<u8, ParseRuleErr> {
+    match token.parse::<usize>() {
+        Ok(state) if state < states => Ok(state as u8),
+        _ => Err(ParseRuleErr(format!("Bad state '{token}'"))),
+    }
+}
+
+/// Split on commas that aren't inside `{...}`
+fn split_entries(line: &str) -> Vec<&str> {
+    let (mut entries, mut depth, mut start) = (vec![], 0, 0);
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(line[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(line[start..].trim());
+
+    entries
+}
+
+fn parse_set(
+    set: &str,
+    states: usize,
+    vars: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, ParseRuleErr> {
+    let inner = set
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| ParseRuleErr(format!("Bad set '{set}'")))?;
+    let mut values = vec![];
+
+    for item in split_entries(inner) {
+        match vars.get(item) {
+            Some(var) => values.extend(var),
+            None => values.push(parse_state(item, states)?),
+        }
+    }
+
+    Ok(values)
+}
+
+/// Where a configuration goes in a dense lookup table
+fn index(cells: &[u8], states: usize) -> usize {
+    cells
+        .iter()
+        .fold(0, |idx, &state| idx * states + state as usize)
+}
+
+struct TableBuilder {
+    states: usize,
+    neighborhood: TableNeighborhood,
+    symmetry: Symmetry,
+    vars: HashMap<String, Vec<u8>>,
+    /// Outputs by canonical configuration, from the first transition matching each
+    transitions: HashMap<Cells, u8>,
+}
+
+impl TableBuilder {
+    fn new(states: usize, neighborhood: TableNeighborhood, symmetry: Symmetry) -> Self {
+        TableBuilder {
+            states,
+            neighborhood,
+            symmetry,
+            vars: HashMap::new(),
+            transitions: HashMap::new(),
+        }
+    }
+
+    /// The configuration with dense lookup index `index`
+    fn cells(&self, mut index: usize) -> Cells {
+        let mut cells = [0; 9];
+        for cell in cells[..self.neighborhood.size()].iter_mut().rev() {
+            *cell = (index % self.states) as u8;
+            index /= self.states;
+        }
+        cells
+    }
+
+    fn parse_var(&mut self, line: &str) -> Result<(), ParseRuleErr> {
+        let (name, set) = line
+            .split_once('=')
+            .ok_or_else(|| ParseRuleErr(format!("Bad variable '{line}'")))?;
+        let set = parse_set(set.trim(), self.states, &self.vars)?;
+
+        self.vars.insert(name.trim().to_owned(), set);
+        Ok(())
+    }
+
+    fn parse_transition(&mut self, line: &str) -> Result<(), ParseRuleErr> {
+        let tokens: Vec<&str> = if line.contains(',') {
+            split_entries(line)
+        } else {
+            // With fewer than 11 states the commas may be omitted
+            line.split("").filter(|s| !s.trim().is_empty()).collect()
+        };
+        if tokens.len() != self.neighborhood.size() + 1 {
+            return Err(ParseRuleErr(format!("Bad transition '{line}'")));
+        }
+
+        let entries = tokens
+            .iter()
+            .map(|&token| {
+                if self.vars.contains_key(token) {
+                    Ok(Entry::Var(token.to_owned()))
+                } else if token.starts_with('{') {
+                    parse_set(token, self.states, &self.vars).map(Entry::Set)
+                } else {
+                    parse_state(token, self.states).map(Entry::State)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A variable used more than once only varies where it's first used
+        let mut seen = vec![];
+        let size = entries[..self.neighborhood.size()]
+            .iter()
+            .try_fold(1usize, |size, entry| {
+                let options = match entry {
+                    Entry::State(_) => 1,
+                    Entry::Set(set) => set.len(),
+                    Entry::Var(name) if seen.contains(&name) => 1,
+                    Entry::Var(name) => {
+                        seen.push(name);
+                        self.vars[name].len()
+                    }
+                };
+                size.checked_mul(options)
+            })
+            .filter(|&size| size <= MAX_TRANSITION_SIZE);
+        if size.is_none() {
+            return Err(ParseRuleErr(format!(
+                "Transition '{line}' matches more than {MAX_TRANSITION_SIZE} neighborhoods"
+            )));
+        }
+
+        let mut cells = vec![0; self.neighborhood.size()];
+        self.add_transitions(&entries, &mut HashMap::new(), &mut cells, 0);
+        Ok(())
+    }
+
+    /// Enumerate every configuration matched by `entries`; a variable used more than once is
+    /// bound to the same value everywhere, including the output
+    fn add_transitions(
+        &mut self,
+        entries: &[Entry],
+        bound: &mut HashMap<String, u8>,
+        cells: &mut Vec<u8>,
+        pos: usize,
+    ) {
+        if pos == cells.len() {
+            let output = match &entries[pos] {
+                Entry::State(state) => *state,
+                Entry::Var(name) => match bound.get(name) {
+                    Some(&state) => state,
+                    // An unbound output variable has no meaning; leave the cell unchanged
+                    None => cells[0],
+                },
+                Entry::Set(_) => cells[0],
+            };
+            let mut canonical = [0; 9];
+            canonical[..pos].copy_from_slice(cells);
+            self.symmetry.canonicalize(&mut canonical[..pos]);
+            self.transitions.entry(canonical).or_insert(output);
+            return;
+        }
+
+        let candidates = match &entries[pos] {
+            Entry::State(state) => vec![*state],
+            Entry::Set(set) => set.clone(),
+            Entry::Var(name) => match bound.get(name) {
+                Some(&state) => vec![state],
+                None => self.vars[name].clone(),
+            },
+        };
+        let binds = match &entries[pos] {
+            Entry::Var(name) if !bound.contains_key(name) => entries[pos + 1..]
+                .iter()
+                .any(|e| matches!(e, Entry::Var(n) if n == name))
+                .then(|| name.clone()),
+            _ => None,
+        };
+
+        for state in candidates {
+            if let Some(name) = &binds {
+                bound.insert(name.clone(), state);
+            }
+            cells[pos] = state;
+            self.add_transitions(entries, bound, cells, pos + 1);
+        }
+        if let Some(name) = binds {
+            bound.remove(&name);
+        }
+    }
+
+    fn build(self, name: String, colors: Vec<Option<[u8; 3]>>) -> RuleTable {
+        let size = self.neighborhood.size();
+        let lookup = match self
+            .states
+            .checked_pow(size as u32)
+            .filter(|&configs| configs <= MAX_DENSE_SIZE)
+        {
+            Some(configs) => Lookup::Dense(
+                (0..configs)
+                    .map(|idx| {
+                        let mut cells = self.cells(idx);
+                        let center = cells[0];
+                        self.symmetry.canonicalize(&mut cells[..size]);
+                        self.transitions.get(&cells).copied().unwrap_or(center)
+                    })
+                    .collect(),
+            ),
+            None => Lookup::Sparse(self.transitions),
+        };
+
+        RuleTable {
+            name,
+            states: self.states as u8,
+            neighborhood: self.neighborhood,
+            symmetry: self.symmetry,
+            lookup,
+            colors,
+        }
+    }
+}
+
+fn parse_color(line: &str) -> Result<(u8, [u8; 3]), ParseRuleErr> {
+    let err = || ParseRuleErr(format!("Bad color '{line}'"));
+    let values = line
+        .split_whitespace()
+        .map(|v| v.parse::<u8>().map_err(|_| err()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match values[..] {
+        [state, r, g, b] => Ok((state, [r, g, b])),
+        _ => Err(err()),
+    }
+}
+
+impl RuleTable {
+    pub(crate) fn load(path: &str) -> Result<RuleTable, ParseRuleErr> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| ParseRuleErr(format!("{path}: {e}")))?;
+
+        RuleTable::parse(&contents)
+    }
+
+    pub(crate) fn parse(contents: &str) -> Result<RuleTable, ParseRuleErr> {
+        let (mut name, mut section) = (String::from("table"), "");
+        let (mut states, mut neighborhood, mut symmetry) = (None, None, String::from("none"));
+        let mut builder: Option<TableBuilder> = None;
+        let mut colors = vec![];
+
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('@') {
+                let mut words = header.split_whitespace();
+                section = words.next().unwrap_or("");
+                if section == "RULE" {
+                    name = words.collect::<Vec<_>>().join(" ");
+                }
+                continue;
+            }
+
+            match section {
+                "TABLE" => {
+                    if let Some((key, value)) = line.split_once(':') {
+                        let value = value.trim();
+                        match key.trim() {
+                            "n_states" => {
+                                states = Some(
+                                    value
+                                        .parse::<usize>()
+                                        .ok()
+                                        .filter(|n| (2..=255).contains(n))
+                                        .ok_or_else(|| {
+                                            ParseRuleErr(format!("Bad n_states '{value}'"))
+                                        })?,
+                                )
+                            }
+                            "neighborhood" => {
+                                neighborhood = Some(match value {
+                                    "Moore" => TableNeighborhood::Moore,
+                                    "vonNeumann" => TableNeighborhood::VonNeumann,
+                                    _ => {
+                                        return Err(ParseRuleErr(format!(
+                                            "Unsupported neighborhood '{value}'"
+                                        )))
+                                    }
+                                })
+                            }
+                            "symmetries" => symmetry = value.to_owned(),
+                            key => return Err(ParseRuleErr(format!("Unknown key '{key}'"))),
+                        }
+                        continue;
+                    }
+
+                    if builder.is_none() {
+                        let neighborhood = neighborhood
+                            .ok_or_else(|| ParseRuleErr("Missing neighborhood".into()))?;
+                        builder = Some(TableBuilder::new(
+                            states.ok_or_else(|| ParseRuleErr("Missing n_states".into()))?,
+                            neighborhood,
+                            Symmetry::parse(&symmetry, neighborhood)?,
+                        ));
+                    }
+                    let builder = builder.as_mut().unwrap();
+
+                    match line.strip_prefix("var ") {
+                        Some(var) => builder.parse_var(var)?,
+                        None => builder.parse_transition(line)?,
+                    }
+                }
+                "COLORS" => {
+                    let (state, color) = parse_color(line)?;
+                    let state = state as usize;
+                    if colors.len() <= state {
+                        colors.resize(state + 1, None);
+                    }
+                    colors[state] = Some(color);
+                }
+                _ => {}
+            }
+        }
+
+        let builder = match builder {
+            Some(builder) => builder,
+            // A table with no transitions still needs a neighborhood and state count
+            None => TableBuilder::new(
+                states.ok_or_else(|| ParseRuleErr("Missing @TABLE".into()))?,
+                neighborhood.ok_or_else(|| ParseRuleErr("Missing neighborhood".into()))?,
+                Symmetry::Permute,
+            ),
+        };
+
+        Ok(builder.build(name, colors))
+    }
+
+    /// The name from the `@RULE` line
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of distinct cell states
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    pub(crate) fn offsets(&self) -> &'static [(isize, isize)] {
+        self.neighborhood.offsets()
+    }
+
+    /// The color the `@COLORS` section gives a state, if any
+    pub fn color(&self, state: u8) -> Option<[u8; 3]> {
+        self.colors.get(state as usize).copied().flatten()
+    }
+
+    /// Next state of a cell, given its neighborhood in the order of `offsets`
+    pub(crate) fn successor(&self, neighborhood: impl Iterator<Item = u8>) -> u8 {
+        let (mut cells, mut size): (Cells, usize) = ([0; 9], 0);
+
+        for state in neighborhood {
+            if state >= self.states {
+                // Out-of-range states (e.g. from a template) are left alone
+                return if size == 0 { state } else { cells[0] };
+            }
+            cells[size] = state;
+            size += 1;
+        }
+
+        match &self.lookup {
+            Lookup::Dense(lookup) => lookup[index(&cells[..size], self.states as usize)],
+            Lookup::Sparse(transitions) => {
+                let center = cells[0];
+                self.symmetry.canonicalize(&mut cells[..size]);
+                transitions.get(&cells).copied().unwrap_or(center)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) const WIREWORLD: &str = "@RULE WireWorld
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={a}
+var c={a}
+var d={a}
+var e={a}
+var f={a}
+var g={a}
+var h={a}
+var i={0,2,3}
+var j={i}
+var k={i}
+var l={i}
+var m={i}
+var n={i}
+var o={i}
+# head becomes tail, tail becomes wire
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+# wire becomes head with one or two head neighbors
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+
+@COLORS
+1 0 128 255
+2 255 255 255
+3 255 128 0
+";
+
+#[test]
+fn test_parse_wireworld() {
+    let table = RuleTable::parse(WIREWORLD).unwrap();
+
+    assert_eq!(table.name(), "WireWorld");
+    assert_eq!(table.states(), 4);
+    assert_eq!(table.color(0), None);
+    assert_eq!(table.color(3), Some([255, 128, 0]));
+    assert_eq!(table.successor([1, 0, 0, 0, 0, 0, 0, 0, 0].into_iter()), 2);
+    assert_eq!(table.successor([2, 3, 3, 3, 3, 3, 3, 3, 3].into_iter()), 3);
+    assert_eq!(table.successor([3, 0, 0, 0, 0, 1, 0, 0, 0].into_iter()), 1);
+    assert_eq!(table.successor([3, 0, 1, 0, 0, 1, 0, 0, 0].into_iter()), 1);
+    assert_eq!(table.successor([3, 1, 1, 1, 0, 0, 0, 0, 0].into_iter()), 3);
+    assert_eq!(table.successor([0, 1, 1, 1, 0, 0, 0, 0, 0].into_iter()), 0);
+    assert_eq!(table.successor([9, 1, 1, 1, 0, 0, 0, 0, 0].into_iter()), 9);
+}
+
+#[test]
+fn test_parse_symmetries_and_bound_vars() {
+    let table = RuleTable::parse(
+        "@TABLE
+n_states:3
+neighborhood:vonNeumann
+symmetries:rotate4
+var a={0,1,2}
+# a cell copies its north neighbor when it matches the south one
+0,a,0,a,0,a
+010002
+",
+    )
+    .unwrap();
+
+    assert_eq!(table.successor([0, 2, 0, 2, 0].into_iter()), 2);
+    // rotated a quarter turn
+    assert_eq!(table.successor([0, 0, 1, 0, 1].into_iter()), 1);
+    assert_eq!(table.successor([0, 1, 0, 2, 0].into_iter()), 0);
+    // the second transition, in each of its rotations
+    assert_eq!(table.successor([0, 1, 0, 0, 0].into_iter()), 2);
+    assert_eq!(table.successor([0, 0, 0, 0, 1].into_iter()), 2);
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = |table: &str| RuleTable::parse(table).unwrap_err().to_string();
+
+    assert_eq!(err("@RULE empty"), "Missing @TABLE");
+    assert_eq!(
+        err("@TABLE\nn_states:2\nneighborhood:hexagonal\n"),
+        "Unsupported neighborhood 'hexagonal'"
+    );
+    assert_eq!(
+        err("@TABLE\nn_states:2\nneighborhood:vonNeumann\nsymmetries:rotate8\n0,0,0,0,0,1"),
+        "Unsupported symmetries 'rotate8' for VonNeumann neighborhood"
+    );
+    assert_eq!(
+        err("@TABLE\nn_states:2\nneighborhood:vonNeumann\n0,0,0,0,0,2"),
+        "Bad state '2'"
+    );
+    assert_eq!(
+        err("@TABLE\nn_states:2\nneighborhood:vonNeumann\n0,0,0,1"),
+        "Bad transition '0,0,0,1'"
+    );
+    assert_eq!(
+        err(
+            "@TABLE\nn_states:9\nneighborhood:Moore\nvar a={0,1,2,3,4,5,6,7,8}\nvar b={a}\n\
+             var c={a}\nvar d={a}\nvar e={a}\nvar f={a}\nvar g={a}\nvar h={a}\n\
+             0,a,b,c,d,e,f,g,h,1"
+        ),
+        "Transition '0,a,b,c,d,e,f,g,h,1' matches more than 16777216 neighborhoods"
+    );
+}
+
+#[test]
+fn test_parse_large_tables() {
+    // Too many Moore configurations to list them all
+    let table = RuleTable::parse(
+        "@TABLE
+n_states:8
+neighborhood:Moore
+symmetries:rotate4reflect
+1,2,0,0,0,0,0,0,0,3
+7,7,7,0,0,0,0,0,0,0
+",
+    )
+    .unwrap();
+
+    assert!(matches!(table.lookup, Lookup::Sparse(_)));
+    assert_eq!(table.successor([1, 2, 0, 0, 0, 0, 0, 0, 0].into_iter()), 3);
+    // rotated a quarter turn
+    assert_eq!(table.successor([1, 0, 0, 2, 0, 0, 0, 0, 0].into_iter()), 3);
+    assert_eq!(table.successor([1, 0, 0, 0, 0, 0, 0, 0, 2].into_iter()), 1);
+    // reflected
+    assert_eq!(table.successor([7, 7, 0, 0, 0, 0, 0, 0, 7].into_iter()), 0);
+    assert_eq!(table.successor([5, 7, 7, 0, 0, 0, 0, 0, 0].into_iter()), 5);
+    assert_eq!(table.successor([9, 2, 0, 0, 0, 0, 0, 0, 0].into_iter()), 9);
+
+    // As many states as von Neumann's 29-state automaton
+    let table = RuleTable::parse(
+        "@TABLE\nn_states:29\nneighborhood:vonNeumann\nsymmetries:rotate4\n0,28,0,0,0,1\n",
+    )
+    .unwrap();
+    assert_eq!(table.states(), 29);
+    assert_eq!(table.successor([0, 0, 28, 0, 0].into_iter()), 1);
+    assert_eq!(table.successor([0, 0, 27, 0, 0].into_iter()), 0);
+
+    assert!(RuleTable::parse("@TABLE\nn_states:255\nneighborhood:Moore\n").is_ok());
+}
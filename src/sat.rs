@@ -0,0 +1,425 @@
+//! A small CDCL SAT solver: two watched literals, first-UIP clause learning,
+//! activity-based branching with phase saving, and Luby restarts.
+
+use std::ops::Not;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Var(u32);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Lit(u32);
+
+impl Var {
+    pub(crate) fn lit(self, value: bool) -> Lit {
+        Lit(self.0 * 2 + !value as u32)
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Lit {
+    fn var(self) -> Var {
+        Var(self.0 / 2)
+    }
+
+    /// The value of the variable that makes this literal true
+    fn sign(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+const RESTART_BASE: usize = 100;
+const ACTIVITY_DECAY: f64 = 0.95;
+
+#[derive(Default)]
+pub(crate) struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// Clauses watching each literal (indexed by `Lit::index`)
+    watches: Vec<Vec<usize>>,
+    assigns: Vec<Option<bool>>,
+    phase: Vec<bool>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    activity: Vec<f64>,
+    activity_inc: f64,
+    trail: Vec<Lit>,
+    trail_lim: Vec<usize>,
+    qhead: usize,
+    unsat: bool,
+}
+
+fn luby(mut i: usize) -> usize {
+    // Find the finite subsequence containing index `i`, and its size
+    let (mut size, mut seq) = (1, 0);
+    while size < i + 1 {
+        seq += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != i {
+        size = (size - 1) >> 1;
+        seq -= 1;
+        i %= size;
+    }
+    1 << seq
+}
+
+impl Solver {
+    pub(crate) fn new() -> Self {
+        Solver {
+            activity_inc: 1.0,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn new_var(&mut self) -> Var {
+        let var = Var(self.assigns.len() as u32);
+        self.assigns.push(None);
+        self.phase.push(false);
+        self.level.push(0);
+        self.reason.push(None);
+        self.activity.push(0.0);
+        self.watches.extend([vec![], vec![]]);
+        var
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit.var().index()].map(|v| v == lit.sign())
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var().index();
+        self.assigns[var] = Some(lit.sign());
+        self.level[var] = self.decision_level();
+        self.reason[var] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Add a clause before solving; returns `false` once the formula is known to be unsatisfiable
+    pub(crate) fn add_clause(&mut self, lits: &[Lit]) -> bool {
+        assert_eq!(self.decision_level(), 0);
+        if self.unsat {
+            return false;
+        }
+
+        let mut clause: Vec<Lit> = vec![];
+        for &lit in lits {
+            match self.value(lit) {
+                Some(true) => return true,
+                Some(false) => {}
+                None if clause.contains(&!lit) => return true,
+                None if !clause.contains(&lit) => clause.push(lit),
+                None => {}
+            }
+        }
+
+        match clause[..] {
+            [] => self.unsat = true,
+            [unit] => {
+                self.enqueue(unit, None);
+                self.unsat = self.propagate().is_some();
+            }
+            _ => {
+                self.attach(clause);
+            }
+        }
+
+        !self.unsat
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let id = self.clauses.len();
+        self.watches[(!clause[0]).index()].push(id);
+        self.watches[(!clause[1]).index()].push(id);
+        self.clauses.push(clause);
+        id
+    }
+
+    /// Unit propagation; returns a conflicting clause if one is found
+    fn propagate(&mut self) -> Option<usize> {
+        while self.qhead < self.trail.len() {
+            let lit = self.trail[self.qhead];
+            self.qhead += 1;
+            let false_lit = !lit;
+            let watching = std::mem::take(&mut self.watches[lit.index()]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+
+            for (i, &id) in watching.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watching[i..]);
+                    break;
+                }
+                let clause = &mut self.clauses[id];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                if self.assigns[first.var().index()] == Some(first.sign()) {
+                    kept.push(id);
+                    continue;
+                }
+
+                let replacement = (2..clause.len()).find(|&k| {
+                    let l = clause[k];
+                    self.assigns[l.var().index()] != Some(!l.sign())
+                });
+                match replacement {
+                    Some(k) => {
+                        clause.swap(1, k);
+                        let watch = !clause[1];
+                        self.watches[watch.index()].push(id);
+                    }
+                    None => {
+                        kept.push(id);
+                        match self.value(first) {
+                            Some(false) => conflict = Some(id),
+                            _ => self.enqueue(first, Some(id)),
+                        }
+                    }
+                }
+            }
+
+            self.watches[lit.index()].extend(kept);
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    fn bump(&mut self, var: Var) {
+        self.activity[var.index()] += self.activity_inc;
+        if self.activity[var.index()] > 1e100 {
+            self.activity.iter_mut().for_each(|a| *a *= 1e-100);
+            self.activity_inc *= 1e-100;
+        }
+    }
+
+    /// First-UIP conflict analysis: the learnt clause (asserting literal first) and the level
+    /// to backtrack to
+    fn analyze(&mut self, mut conflict: usize) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.assigns.len()];
+        let mut learnt = vec![Lit(0)];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut asserting = None;
+
+        loop {
+            let clause = self.clauses[conflict].clone();
+            let skip = asserting.is_some() as usize;
+            for &lit in &clause[skip..] {
+                let var = lit.var();
+                if !seen[var.index()] && self.level[var.index()] > 0 {
+                    seen[var.index()] = true;
+                    self.bump(var);
+                    if self.level[var.index()] == self.decision_level() {
+                        pending += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            let lit = loop {
+                index -= 1;
+                let lit = self.trail[index];
+                if seen[lit.var().index()] {
+                    break lit;
+                }
+            };
+            seen[lit.var().index()] = false;
+            pending -= 1;
+            asserting = Some(lit);
+            if pending == 0 {
+                break;
+            }
+            conflict = self.reason[lit.var().index()].expect("implied literal without a reason");
+        }
+
+        learnt[0] = !asserting.unwrap();
+        let backtrack = match learnt.len() {
+            1 => 0,
+            _ => {
+                let (max, _) = learnt[1..]
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, lit)| self.level[lit.var().index()])
+                    .unwrap();
+                learnt.swap(1, max + 1);
+                self.level[learnt[1].var().index()]
+            }
+        };
+        self.activity_inc /= ACTIVITY_DECAY;
+
+        (learnt, backtrack)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() > level {
+            let start = self.trail_lim[level];
+            for lit in self.trail.drain(start..) {
+                let var = lit.var().index();
+                self.phase[var] = lit.sign();
+                self.assigns[var] = None;
+                self.reason[var] = None;
+            }
+            self.trail_lim.truncate(level);
+            self.qhead = self.trail.len();
+        }
+    }
+
+    fn pick_branch(&self) -> Option<Lit> {
+        (0..self.assigns.len())
+            .filter(|&v| self.assigns[v].is_none())
+            .max_by(|&a, &b| self.activity[a].total_cmp(&self.activity[b]))
+            .map(|v| Var(v as u32).lit(self.phase[v]))
+    }
+
+    /// Search for a satisfying assignment, returning the value of every variable if one exists
+    pub(crate) fn solve(&mut self) -> Option<Vec<bool>> {
+        if self.unsat || self.propagate().is_some() {
+            self.unsat = true;
+            return None;
+        }
+
+        let (mut restarts, mut conflicts) = (0, 0);
+
+        loop {
+            match self.propagate() {
+                Some(conflict) => {
+                    if self.decision_level() == 0 {
+                        self.unsat = true;
+                        return None;
+                    }
+                    conflicts += 1;
+                    let (learnt, level) = self.analyze(conflict);
+                    self.backtrack(level);
+                    match learnt[..] {
+                        [unit] => self.enqueue(unit, None),
+                        _ => {
+                            let asserting = learnt[0];
+                            let id = self.attach(learnt);
+                            self.enqueue(asserting, Some(id));
+                        }
+                    }
+                }
+                None => {
+                    if conflicts >= luby(restarts) * RESTART_BASE {
+                        restarts += 1;
+                        conflicts = 0;
+                        self.backtrack(0);
+                    }
+                    match self.pick_branch() {
+                        Some(lit) => {
+                            self.trail_lim.push(self.trail.len());
+                            self.enqueue(lit, None);
+                        }
+                        None => {
+                            let model = self.assigns.iter().map(|v| v.unwrap()).collect();
+                            self.backtrack(0);
+                            return Some(model);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn satisfies(clauses: &[Vec<Lit>], model: &[bool]) -> bool {
+    clauses.iter().all(|clause| {
+        clause
+            .iter()
+            .any(|lit| model[lit.var().index()] == lit.sign())
+    })
+}
+
+#[test]
+fn test_luby() {
+    let seq: Vec<usize> = (0..15).map(luby).collect();
+
+    assert_eq!(seq, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+}
+
+#[test]
+fn test_solve_trivial() {
+    let mut solver = Solver::new();
+    let (a, b) = (solver.new_var(), solver.new_var());
+
+    assert!(solver.add_clause(&[a.lit(true), b.lit(true)]));
+    assert!(solver.add_clause(&[a.lit(false)]));
+    assert_eq!(solver.solve(), Some(vec![false, true]));
+
+    assert!(!solver.add_clause(&[b.lit(false)]));
+    assert_eq!(solver.solve(), None);
+}
+
+#[test]
+fn test_solve_pigeonhole() {
+    // Four pigeons can't fit in three holes, one per hole
+    let (pigeons, holes) = (4, 3);
+    let mut solver = Solver::new();
+    let vars: Vec<Vec<Var>> = (0..pigeons)
+        .map(|_| (0..holes).map(|_| solver.new_var()).collect())
+        .collect();
+
+    for pigeon in &vars {
+        let lits: Vec<Lit> = pigeon.iter().map(|v| v.lit(true)).collect();
+        solver.add_clause(&lits);
+    }
+    // No two pigeons share a hole
+    for (p, pigeon) in vars.iter().enumerate() {
+        for other in &vars[p + 1..] {
+            for (a, b) in pigeon.iter().zip(other) {
+                solver.add_clause(&[a.lit(false), b.lit(false)]);
+            }
+        }
+    }
+
+    assert_eq!(solver.solve(), None);
+}
+
+#[test]
+fn test_solve_random_3sat() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for _ in 0..20 {
+        let mut solver = Solver::new();
+        let vars: Vec<Var> = (0..30).map(|_| solver.new_var()).collect();
+        let clauses: Vec<Vec<Lit>> = (0..120)
+            .map(|_| {
+                (0..3)
+                    .map(|_| vars[rng.random_range(0..vars.len())].lit(rng.random()))
+                    .collect()
+            })
+            .collect();
+        let consistent = clauses.iter().all(|clause| solver.add_clause(clause));
+
+        if let Some(model) = solver.solve() {
+            assert!(consistent);
+            assert!(satisfies(&clauses, &model));
+        }
+    }
+}
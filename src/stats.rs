@@ -0,0 +1,117 @@
+use crate::board::Board;
+#[cfg(test)]
+use std::str::FromStr;
+
+/// Per-cell history kept alongside a board, for coloring by age, trails or activity
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CellStats {
+    rows: usize,
+    cols: usize,
+    generation: usize,
+    live: Vec<bool>,
+    /// Generations each live cell has been alive (0 for dead cells)
+    age: Vec<u32>,
+    /// Generations since each dead cell last died (`u32::MAX` if never seen alive)
+    since_death: Vec<u32>,
+    /// How many times each cell has been born or died
+    activity: Vec<u32>,
+}
+
+impl CellStats {
+    pub(crate) fn new(brd: &Board) -> Self {
+        let live: Vec<bool> = brd.iter().collect();
+
+        CellStats {
+            rows: brd.rows(),
+            cols: brd.cols(),
+            generation: brd.generation(),
+            age: live.iter().map(|&alive| alive as u32).collect(),
+            since_death: vec![u32::MAX; live.len()],
+            activity: vec![0; live.len()],
+            live,
+        }
+    }
+
+    /// Catch up with `brd`: cells age if it's the next generation, otherwise (after an edit,
+    /// undo or jump) only cells that changed are updated. A resized board starts afresh.
+    pub(crate) fn update(&mut self, brd: &Board) {
+        if (brd.rows(), brd.cols()) != (self.rows, self.cols) {
+            *self = Self::new(brd);
+            return;
+        }
+        let advance = brd.generation() == self.generation + 1;
+
+        for (i, alive) in brd.iter().enumerate() {
+            match (self.live[i], alive) {
+                (true, true) if advance => self.age[i] = self.age[i].saturating_add(1),
+                (false, false) if advance => {
+                    self.since_death[i] = self.since_death[i].saturating_add(1)
+                }
+                (true, false) => {
+                    self.age[i] = 0;
+                    self.since_death[i] = 0;
+                    self.activity[i] += 1;
+                }
+                (false, true) => {
+                    self.age[i] = 1;
+                    self.activity[i] += 1;
+                }
+                _ => {}
+            }
+            self.live[i] = alive;
+        }
+        self.generation = brd.generation();
+    }
+
+    pub(crate) fn age(&self, i: usize) -> u32 {
+        self.age[i]
+    }
+
+    pub(crate) fn since_death(&self, i: usize) -> u32 {
+        self.since_death[i]
+    }
+
+    pub(crate) fn activity(&self, i: usize) -> u32 {
+        self.activity[i]
+    }
+
+    pub(crate) fn max_activity(&self) -> u32 {
+        self.activity.iter().copied().max().unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_age() {
+    let brd = Board::from_str("...\n@@@\n...").unwrap();
+    let mut stats = CellStats::new(&brd);
+    let next = brd.next_generation();
+
+    stats.update(&next);
+    assert_eq!(stats.age(4), 2);
+    assert_eq!(stats.age(1), 1);
+    assert_eq!(stats.age(3), 0);
+    assert_eq!(stats.since_death(3), 0);
+    assert_eq!(stats.since_death(0), u32::MAX);
+
+    stats.update(&next.next_generation());
+    assert_eq!(stats.age(4), 3);
+    assert_eq!(stats.since_death(1), 0);
+    assert_eq!(stats.activity(1), 2);
+    assert_eq!(stats.max_activity(), 2);
+}
+
+#[test]
+fn test_update_after_edit() {
+    let brd = Board::from_str("@@\n@@").unwrap();
+    let mut stats = CellStats::new(&brd.next_generation());
+
+    // Editing in the same generation doesn't age the other cells
+    let mut edited = brd.next_generation();
+    edited.set(0, 0, 0);
+    stats.update(&edited);
+    assert_eq!((stats.age(0), stats.age(1)), (0, 1));
+    assert_eq!(stats.since_death(0), 0);
+
+    stats.update(&edited.pad(1, 1, 1, 1));
+    assert_eq!(stats.max_activity(), 0);
+}
@@ -0,0 +1,188 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers,
+};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute};
+
+use crate::board::Board;
+use crate::render::Output;
+use crate::CLEAR;
+
+/// Raw mode and the alternate screen (with the cursor hidden) for as long as it lives,
+/// restoring the terminal when dropped, even on panic
+pub(crate) struct RawTerminal {
+    mouse: bool,
+}
+
+impl RawTerminal {
+    /// Take over the terminal, also capturing mouse events if `mouse` is set
+    pub(crate) fn enter(mouse: bool) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        if mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        Ok(RawTerminal { mouse })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        if self.mouse {
+            let _ = execute!(io::stdout(), DisableMouseCapture);
+        }
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Whether a key quits: q, Esc or (as raw mode doesn't turn it into a signal) Ctrl+C
+pub(crate) fn is_quit(key: KeyEvent) -> bool {
+    key.kind != KeyEventKind::Release
+        && match key.code {
+            KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+            KeyCode::Char('q') | KeyCode::Esc => true,
+            _ => false,
+        }
+}
+
+/// What is on the terminal, so each frame only redraws the characters that changed
+#[derive(Default)]
+pub(crate) struct Screen {
+    rows: Vec<Vec<String>>,
+}
+
+impl Screen {
+    /// The text and cursor movements turning the screen into `frame`, a grid of characters
+    /// (each with any color escapes). The whole screen is redrawn if its shape changes.
+    pub(crate) fn update(&mut self, frame: Vec<Vec<String>>) -> String {
+        let mut out = String::new();
+        let reshaped = frame.len() != self.rows.len()
+            || frame
+                .iter()
+                .zip(&self.rows)
+                .any(|(new, old)| new.len() != old.len());
+        if reshaped {
+            out.push_str(CLEAR);
+            self.rows.clear();
+        }
+
+        for (y, row) in frame.iter().enumerate() {
+            let old = self.rows.get(y);
+            // Where writing leaves the cursor, to skip moving it for consecutive changes
+            let mut next = None;
+
+            for (x, cell) in row.iter().enumerate() {
+                if old.and_then(|old| old.get(x)) == Some(cell) {
+                    continue;
+                }
+                if next != Some(x) {
+                    let _ = write!(out, "\x1b[{};{}H", y + 1, x + 1);
+                }
+                out.push_str(cell);
+                next = Some(x + 1);
+            }
+        }
+
+        self.rows = frame;
+        out
+    }
+
+    /// Everything on the screen, one line per row
+    pub(crate) fn text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.concat())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Wait until `deadline`, returning early with true if a quit key is pressed
+fn wait(deadline: Instant) -> io::Result<bool> {
+    while event::poll(deadline.saturating_duration_since(Instant::now()))? {
+        if let Event::Key(key) = event::read()? {
+            if is_quit(key) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Show each generation up to `run_gens` in place on the alternate screen, redrawing only
+/// what changed, until done or quit with q, Esc or Ctrl+C. The last frame is printed again
+/// afterwards so it stays on the normal screen.
+pub(crate) fn play(
+    mut brd: Board,
+    ups: u64,
+    run_gens: Option<usize>,
+    output: Output,
+) -> io::Result<()> {
+    let frame_time = Duration::from_secs_f64(1.0 / ups as f64);
+    let mut screen = Screen::default();
+
+    {
+        let _terminal = RawTerminal::enter(false)?;
+        let mut stdout = io::stdout();
+
+        while Some(brd.generation()) <= run_gens {
+            let deadline = Instant::now() + frame_time;
+            write!(stdout, "{}", screen.update(output.cells(&brd)))?;
+            stdout.flush()?;
+
+            if wait(deadline)? || Some(brd.generation()) == run_gens {
+                break;
+            }
+            brd.step_in_place();
+        }
+    }
+
+    println!("{}", screen.text());
+    Ok(())
+}
+
+#[cfg(test)]
+fn frame(text: &str) -> Vec<Vec<String>> {
+    text.lines()
+        .map(|line| line.chars().map(String::from).collect())
+        .collect()
+}
+
+#[test]
+fn test_screen() {
+    let mut screen = Screen::default();
+
+    assert_eq!(
+        screen.update(frame("...\n@@@")),
+        format!("{CLEAR}\x1b[1;1H...\x1b[2;1H@@@")
+    );
+    assert_eq!(screen.update(frame("...\n@@@")), "");
+    assert_eq!(
+        screen.update(frame(".@.\n.@.")),
+        "\x1b[1;2H@\x1b[2;1H.\x1b[2;3H."
+    );
+    assert_eq!(screen.update(frame("@@.\n...")), "\x1b[1;1H@\x1b[2;2H.");
+    assert_eq!(screen.text(), "@@.\n...");
+
+    // A new shape (e.g. after the terminal is resized) redraws everything
+    assert_eq!(
+        screen.update(frame("@@\n..")),
+        format!("{CLEAR}\x1b[1;1H@@\x1b[2;1H..")
+    );
+}
+
+#[test]
+fn test_is_quit() {
+    let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+    assert!(is_quit(key(KeyCode::Char('q'), KeyModifiers::NONE)));
+    assert!(is_quit(key(KeyCode::Esc, KeyModifiers::NONE)));
+    assert!(is_quit(key(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+    assert!(!is_quit(key(KeyCode::Char('c'), KeyModifiers::NONE)));
+}
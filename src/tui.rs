@@ -0,0 +1,361 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, queue};
+
+use crate::board::{state_char, Board};
+use crate::terminal::{is_quit, RawTerminal};
+
+#[cfg(test)]
+use crossterm::event::KeyModifiers;
+
+const MIN_UPS: u64 = 1;
+const MAX_UPS: u64 = 1000;
+/// How long to wait for input while paused before redrawing anyway
+const IDLE_POLL: Duration = Duration::from_millis(500);
+
+/// The interactive terminal UI: a board with a cursor, shown one character per cell
+pub(crate) struct Tui {
+    brd: Board,
+    /// The cell under the cursor as (row, column)
+    cursor: (usize, usize),
+    /// The cell at the top left of the view as (row, column)
+    view: (usize, usize),
+    /// How many board rows fit above the status line
+    view_rows: usize,
+    running: bool,
+    ups: u64,
+    step_size: usize,
+    generation_limit: Option<usize>,
+}
+
+impl Tui {
+    pub(crate) fn new(
+        brd: Board,
+        ups: u64,
+        running: bool,
+        step_size: usize,
+        generation_limit: Option<usize>,
+    ) -> Self {
+        Tui {
+            cursor: (brd.rows() / 2, brd.cols() / 2),
+            view: (0, 0),
+            view_rows: 0,
+            brd,
+            running,
+            ups: ups.clamp(MIN_UPS, MAX_UPS),
+            step_size,
+            generation_limit,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.generation_limit
+            .is_some_and(|limit| self.brd.generation() >= limit)
+    }
+
+    /// Advance while running, pausing at the generation limit
+    fn run_generation(&mut self) {
+        if self.finished() {
+            self.running = false;
+        } else {
+            self.brd.step_in_place();
+        }
+    }
+
+    fn step(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.brd.step_in_place();
+        }
+    }
+
+    fn move_cursor(&mut self, d_row: isize, d_col: isize) {
+        let clamp = |pos: usize, delta: isize, len: usize| {
+            pos.saturating_add_signed(delta).min(len.saturating_sub(1))
+        };
+
+        self.cursor = (
+            clamp(self.cursor.0, d_row, self.brd.rows()),
+            clamp(self.cursor.1, d_col, self.brd.cols()),
+        );
+    }
+
+    fn toggle(&mut self, (row, col): (usize, usize)) {
+        self.brd
+            .set(row, col, self.brd.next_state(self.brd.cell(row, col)));
+    }
+
+    /// Handle a key press, returning false to quit
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return true;
+        }
+        if is_quit(key) {
+            return false;
+        }
+
+        match key.code {
+            KeyCode::Char(' ') => self.running = !self.running,
+            KeyCode::Char('s') => self.step(1),
+            KeyCode::Char('n') => self.step(self.step_size),
+            KeyCode::Char('c') => self.brd = self.brd.clear(),
+            KeyCode::Char('r') => self.brd = self.brd.random(),
+            KeyCode::Char('[') => self.ups = (self.ups / 2).max(MIN_UPS),
+            KeyCode::Char(']') => self.ups = (self.ups * 2).min(MAX_UPS),
+            KeyCode::Enter | KeyCode::Char('x') => self.toggle(self.cursor),
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(0, -1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(0, 1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1, 0),
+            _ => {}
+        }
+        true
+    }
+
+    /// Clicking a cell moves the cursor there and toggles it
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+            let cell = (
+                self.view.0 + mouse.row as usize,
+                self.view.1 + mouse.column as usize,
+            );
+
+            if (mouse.row as usize) < self.view_rows
+                && cell.0 < self.brd.rows()
+                && cell.1 < self.brd.cols()
+            {
+                self.cursor = cell;
+                self.toggle(cell);
+            }
+        }
+    }
+
+    /// Scroll the view as little as possible to keep the cursor within `rows` by `cols`,
+    /// without leaving space past the edge of the board
+    fn scroll_to_cursor(&mut self, rows: usize, cols: usize) {
+        let scroll = |view: usize, cursor: usize, screen: usize, len: usize| {
+            view.clamp((cursor + 1).saturating_sub(screen), cursor)
+                .min(len.saturating_sub(screen))
+        };
+
+        self.view = (
+            scroll(self.view.0, self.cursor.0, rows, self.brd.rows()),
+            scroll(self.view.1, self.cursor.1, cols, self.brd.cols()),
+        );
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "Gen {} | Pop {} | {} ups | {} | Cursor {},{} | Space pause, s step, n step {}, x toggle, q quit",
+            self.brd.generation(),
+            self.brd.population(),
+            self.ups,
+            if self.running { "running" } else { "paused" },
+            self.cursor.0,
+            self.cursor.1,
+            self.step_size,
+        )
+    }
+
+    /// The lines of a `width` by `height` screen: the visible part of the board with a status
+    /// line below it
+    fn frame(&mut self, width: usize, height: usize) -> Vec<String> {
+        let rows = height.saturating_sub(1);
+        self.view_rows = rows;
+        self.scroll_to_cursor(rows, width);
+
+        let (top, left) = self.view;
+        let mut lines: Vec<String> = (top..self.brd.rows().min(top + rows))
+            .map(|row| {
+                (left..self.brd.cols().min(left + width))
+                    .map(|col| state_char(self.brd.cell(row, col)))
+                    .collect()
+            })
+            .collect();
+
+        lines.resize(rows, String::new());
+        lines.push(self.status().chars().take(width).collect());
+        lines
+    }
+
+    fn draw(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+
+        for (y, line) in self
+            .frame(width as usize, height as usize)
+            .iter()
+            .enumerate()
+        {
+            queue!(
+                out,
+                cursor::MoveTo(0, y as u16),
+                Print(line),
+                terminal::Clear(ClearType::UntilNewLine)
+            )?;
+        }
+
+        let (row, col) = self.cursor;
+        if let (Ok(x), Ok(y)) = (
+            u16::try_from(col - self.view.1),
+            u16::try_from(row - self.view.0),
+        ) {
+            if self.brd.rows() > 0 && self.brd.cols() > 0 && y + 1 < height && x < width {
+                queue!(
+                    out,
+                    cursor::MoveTo(x, y),
+                    SetAttribute(Attribute::Reverse),
+                    Print(state_char(self.brd.cell(row, col))),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            }
+        }
+        out.flush()
+    }
+}
+
+pub(crate) fn run(mut tui: Tui) -> io::Result<()> {
+    let _terminal = RawTerminal::enter(true)?;
+    let mut stdout = io::stdout();
+    let mut next_update = Instant::now();
+
+    loop {
+        tui.draw(&mut stdout)?;
+
+        let timeout = if tui.running {
+            next_update.saturating_duration_since(Instant::now())
+        } else {
+            IDLE_POLL
+        };
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) if !tui.handle_key(key) => return Ok(()),
+                Event::Mouse(mouse) => tui.handle_mouse(mouse),
+                _ => {}
+            }
+        }
+
+        if tui.running && Instant::now() >= next_update {
+            tui.run_generation();
+            next_update = Instant::now() + Duration::from_secs_f64(1.0 / tui.ups as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+fn press(tui: &mut Tui, code: KeyCode) -> bool {
+    tui.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn test_keys() {
+    use std::str::FromStr;
+
+    let mut tui = Tui::new(
+        Board::from_str("...\n...\n...").unwrap(),
+        10,
+        true,
+        10,
+        None,
+    );
+
+    assert_eq!(tui.cursor, (1, 1));
+    press(&mut tui, KeyCode::Char(' '));
+    assert!(!tui.running);
+
+    press(&mut tui, KeyCode::Char('x'));
+    press(&mut tui, KeyCode::Left);
+    press(&mut tui, KeyCode::Enter);
+    press(&mut tui, KeyCode::Char('l'));
+    press(&mut tui, KeyCode::Char('l'));
+    press(&mut tui, KeyCode::Char('l'));
+    press(&mut tui, KeyCode::Char('x'));
+    assert_eq!(tui.brd.to_string(), "...\n@@@\n...");
+    assert_eq!(tui.cursor, (1, 2));
+
+    press(&mut tui, KeyCode::Char('s'));
+    assert_eq!(tui.brd.to_string(), ".@.\n.@.\n.@.");
+    press(&mut tui, KeyCode::Char('n'));
+    assert_eq!(tui.brd.generation(), 11);
+
+    press(&mut tui, KeyCode::Char(']'));
+    assert_eq!(tui.ups, 20);
+    for _ in 0..10 {
+        press(&mut tui, KeyCode::Char('['));
+    }
+    assert_eq!(tui.ups, MIN_UPS);
+
+    press(&mut tui, KeyCode::Char('c'));
+    assert_eq!(tui.brd.population(), 0);
+
+    assert!(!press(&mut tui, KeyCode::Char('q')));
+    assert!(!tui.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+}
+
+#[test]
+fn test_mouse() {
+    let mut tui = Tui::new(Board::new(4, 4), 10, false, 10, None);
+    tui.frame(10, 6);
+    let click = |column, row| MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+
+    tui.handle_mouse(click(3, 0));
+    tui.handle_mouse(click(9, 1));
+    // Clicks on the status line are ignored
+    tui.handle_mouse(click(0, 5));
+    assert_eq!(tui.cursor, (0, 3));
+    assert_eq!(tui.brd.to_string(), "...@\n....\n....\n....");
+}
+
+#[test]
+fn test_frame() {
+    let mut tui = Tui::new(Board::new(10, 10), 10, false, 10, None);
+    tui.toggle((9, 9));
+
+    let frame = tui.frame(4, 3);
+    assert_eq!(frame[..2], ["....", "...."]);
+    assert_eq!(frame[2], "Gen ");
+    assert_eq!(tui.view, (4, 2));
+
+    // The view scrolls to follow the cursor
+    for _ in 0..5 {
+        tui.move_cursor(1, 1);
+    }
+    assert_eq!(tui.cursor, (9, 9));
+    assert_eq!(tui.frame(4, 3)[..2], ["....", "...@"]);
+
+    let frame = tui.frame(60, 12);
+    assert_eq!(frame.len(), 12);
+    assert_eq!(frame[10], "");
+    assert!(frame[11].starts_with("Gen 0 | Pop 1 | 10 ups | paused | Cursor 9,9"));
+}
+
+#[test]
+fn test_generation_limit() {
+    let mut tui = Tui::new(Board::new(3, 3), 10, true, 10, Some(2));
+
+    for _ in 0..5 {
+        tui.run_generation();
+    }
+    assert_eq!(tui.brd.generation(), 2);
+    assert!(!tui.running);
+}
+
+#[test]
+fn test_no_generation_limit() {
+    let mut tui = Tui::new(Board::new(3, 3), 10, true, 10, None);
+
+    for _ in 0..5 {
+        tui.run_generation();
+    }
+    assert_eq!(tui.brd.generation(), 5);
+    assert!(tui.running);
+}
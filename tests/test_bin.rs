@@ -13,7 +13,7 @@ fn test_help() {
 #[test]
 fn test_cli() {
     bin()
-        .args(&[
+        .args([
             #[cfg(feature = "gui")]
             "--no-gui",
             "-g0",
@@ -133,6 +133,254 @@ fn test_cli_generations() {
         .success();
 }
 
+#[test]
+fn test_cli_rule() {
+    let base_args = &[
+        #[cfg(feature = "gui")]
+        "--no-gui",
+        "-t",
+        "...\n@@@\n...",
+        "-p0",
+        "-g1",
+    ][..];
+
+    bin()
+        .args([base_args, &["-R", "B3/S"]].concat())
+        .assert()
+        .stdout(".@.\n...\n.@.\n")
+        .success();
+
+    bin()
+        .args([base_args, &["--rule", "R1,C0,M0,S2..3,B3..3,NM"]].concat())
+        .assert()
+        .stdout(".@.\n.@.\n.@.\n")
+        .success();
+
+    bin()
+        .args([base_args, &["--rule", "B3/Sx"]].concat())
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_rule_file() {
+    let path = std::env::temp_dir().join("rust-life-test-wireworld.rule");
+    std::fs::write(
+        &path,
+        "@RULE WireWorld\n@TABLE\nn_states:4\nneighborhood:vonNeumann\nsymmetries:permute\n\
+         var a={0,1,2,3}\nvar b={a}\nvar c={a}\nvar d={a}\nvar i={0,2,3}\nvar j={i}\nvar k={i}\n\
+         1,a,b,c,d,2\n2,a,b,c,d,3\n3,1,i,j,k,1\n3,1,1,i,j,1\n",
+    )
+    .unwrap();
+
+    bin()
+        .args([
+            #[cfg(feature = "gui")]
+            "--no-gui",
+            "-t",
+            "CBACC",
+            "-p0",
+            "-g1",
+            "--rule-file",
+            path.to_str().unwrap(),
+        ])
+        .assert()
+        .stdout("CCB@C\n")
+        .success();
+
+    // Random soups use every state of the table
+    let soup = bin()
+        .args([
+            #[cfg(feature = "gui")]
+            "--no-gui",
+            "-r4",
+            "-c30",
+            "-g0",
+            "--rule-file",
+            path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let soup = String::from_utf8_lossy(&soup.get_output().stdout).into_owned();
+    assert!(soup.contains('B') && soup.contains('C'), "{soup}");
+
+    bin()
+        .args(["--rule-file", "/nonexistent.rule"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_predecessor() {
+    bin()
+        .args(["predecessor", "-t", "@"])
+        .assert()
+        .stdout("No predecessor exists within the 1x1 box (Garden of Eden)\n")
+        .success();
+
+    bin()
+        .args(["predecessor", "-t", "@", "-R", "B0/S"])
+        .assert()
+        .stdout(".\n")
+        .success();
+
+    bin()
+        .args(["predecessor", "-t", "@@", "-R", "R1,C0,M0,S2..3,B3..3,NM"])
+        .assert()
+        .failure();
+
+    bin().args(["predecessor"]).assert().failure();
+}
+
+#[test]
+fn test_patterns() {
+    let list = bin().args(["patterns", "list"]).output().unwrap();
+
+    assert!(list.status.success());
+    assert!(String::from_utf8_lossy(&list.stdout)
+        .lines()
+        .any(|line| line == "glider          The smallest spaceship, travelling diagonally"));
+
+    bin()
+        .args(["patterns", "show", "blinker"])
+        .assert()
+        .stdout(
+            "blinker: The smallest oscillator\n\
+             Period: 2\n\
+             Discovered: John Conway, 1969\n\
+             \n\
+             @@@\n",
+        )
+        .success();
+
+    bin()
+        .args(["patterns", "show", "puppies"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_template_name() {
+    let base_args = &[
+        #[cfg(feature = "gui")]
+        "--no-gui",
+        "-g0",
+        "-t",
+    ][..];
+
+    bin()
+        .args([base_args, &["glider", "-c4", "-r4"]].concat())
+        .assert()
+        .stdout(".@..\n..@.\n@@@.\n....\n")
+        .success();
+
+    bin()
+        .args([base_args, &["gilder"]].concat())
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_render() {
+    let base_args = &[
+        #[cfg(feature = "gui")]
+        "--no-gui",
+        "-g0",
+        "-tglider",
+        "-c4",
+        "-r4",
+        "--render",
+    ][..];
+
+    bin()
+        .args([base_args, &["half"]].concat())
+        .assert()
+        .stdout(" ▀▄ \n▀▀▀ \n")
+        .success();
+
+    bin()
+        .args([base_args, &["braille"]].concat())
+        .assert()
+        .stdout("⠬⠆\n")
+        .success();
+
+    bin()
+        .args([base_args, &["braille", "--ansi"]].concat())
+        .assert()
+        .stdout("\x1b[37m⠬\x1b[0m\x1b[37m⠆\x1b[0m\n")
+        .success();
+}
+
+#[test]
+fn test_cli_engine() {
+    let base_args = &[
+        #[cfg(feature = "gui")]
+        "--no-gui",
+        "-tglider",
+        "-c6",
+        "-r6",
+        "-atop-left",
+        "-g4",
+    ][..];
+    let engines = [
+        "serial",
+        #[cfg(feature = "rayon")]
+        "rayon",
+        "bitwise",
+        "hashlife",
+        "sparse",
+        "tiled",
+    ];
+
+    for engine in engines {
+        bin()
+            .args([base_args, &["--engine", engine]].concat())
+            .assert()
+            .stdout("......\n..@...\n...@..\n.@@@..\n......\n......\n")
+            .success();
+    }
+
+    bin()
+        .args([base_args, &["--engine", "quantum"]].concat())
+        .assert()
+        .failure();
+}
+
+#[test]
+#[cfg(feature = "gui")]
+fn test_export() {
+    let path = std::env::temp_dir().join(format!("rust-life-export-{}.png", std::process::id()));
+
+    bin()
+        .args(["-t", "glider", "-c6", "-r5", "-g3", "-s2"])
+        .args([
+            "--color-mode",
+            "trails",
+            "--theme",
+            "light",
+            "--live-color",
+            "#c00",
+        ])
+        .arg("--export")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+
+    bin()
+        .args(["--export", "board.not-an-image-format"])
+        .assert()
+        .failure();
+
+    bin()
+        .args(["--export", "board.png", "--live-color", "#12345"])
+        .assert()
+        .failure();
+}
+
 #[test]
 #[cfg(feature = "gui")]
 fn test_gui_scale() {